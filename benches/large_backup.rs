@@ -0,0 +1,108 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use financisto2bluecoins::*;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::*;
+
+/// Pseudo random numbers, the same on every run.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+}
+
+/// A book of the given number of accounts, 10 top-level categories of 20 children each, and about
+/// the given number of transactions: singles, transfers and splits of three lines.
+fn synthetic(transactions: usize, accounts: u64) -> Financisto {
+    let mut random = Lcg(42);
+    let mut book = BackupBuilder::new();
+    let twd = book.currency("TWD");
+    for id in 1..=accounts {
+        book.account(&format!("Account {}", id), "BANK", twd);
+    }
+    let mut children = Vec::new();
+    for parent in 0..10 {
+        for child in 0..20 {
+            children.push(book.category(&format!("Parent {}:Child {}", parent, child)));
+        }
+    }
+
+    let mut count = 0;
+    while count < transactions {
+        let account = random.next(accounts) as i32 + 1;
+        let amount = -(random.next(100000) as i64) - 1;
+        let category = children[random.next(children.len() as u64) as usize];
+        match random.next(10) {
+            0 => {
+                book.transfer(account, account % accounts as i32 + 1, -amount);
+                count += 1;
+            }
+            1 => {
+                let split = book.split(account).id;
+                for _ in 0..3 {
+                    book.split_line(split, category, amount);
+                }
+                count += 4;
+            }
+            _ => {
+                let tx = book.transaction(account, category, amount);
+                tx.note = Some(format!("note {}", tx.id % 500));
+                tx.status = Some("RC".to_owned());
+                count += 1;
+            }
+        }
+    }
+
+    book.build()
+}
+
+fn parse(c: &mut Criterion) {
+    let text = backup_text(&synthetic(200_000, 20));
+    let lines = || text.split('\n').map(|l| l.to_owned()).collect::<Vec<_>>();
+    // both build the same data, the raw entities included, so only the way they read it differs
+    assert_eq!(
+        parse_backup(&text).unwrap(),
+        convert_maps(&lines()).unwrap()
+    );
+    let mut group = c.benchmark_group("parse");
+    group.sample_size(10);
+
+    group.bench_function("maps", |b| b.iter(|| convert_maps(&lines()).unwrap()));
+    group.bench_function("borrowed", |b| b.iter(|| parse_backup(&text).unwrap()));
+
+    group.finish();
+}
+
+fn migrate(c: &mut Criterion) {
+    let options = MigrateOptions::default();
+    let mut group = c.benchmark_group("migrate");
+    group.sample_size(10);
+
+    // an account per 100 transactions, so that a scan per account would show
+    for size in [10_000, 200_000] {
+        let data = synthetic(size, size as u64 / 100);
+        group.bench_with_input(BenchmarkId::new("accounts", size), &data, |b, data| {
+            b.iter(|| migrate_accounts(data, &options).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("transactions", size), &data, |b, data| {
+            b.iter(|| migrate_transactions(data, &options).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("categories", size), &data, |b, data| {
+            b.iter(|| migrate_categories(data, &options).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse, migrate);
+criterion_main!(benches);
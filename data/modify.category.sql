@@ -1,166 +0,0 @@
-
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 2 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 3 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 44 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 44 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 30 where itemID = 57 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 63 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 63 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 43 where itemID = 78 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 41 where itemID = 133 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 145 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 145 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 156 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 156 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 157 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 36 where itemID = 170 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 170 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 189 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 191 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 41 where itemID = 228 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 247 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 36 where itemID = 256 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 256 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 259 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 388 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 40 where itemID = 425 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 451 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 40 where itemID = 517 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 23 where itemID = 550 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 41 where itemID = 580 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 54 where itemID = 613 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 619 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 627 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 647 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 647 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 725 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 725 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 772 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 792 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 808 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 809 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 38 where itemID = 810 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 818 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 858 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 859 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 859 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 869 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 869 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 870 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 887 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 902 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 29 where itemID = 903 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 41 where itemID = 930 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 952 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 954 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 954 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 955 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 37 where itemID = 956 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 38 where itemID = 970 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 989 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 989 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 992 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 996 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 38 where itemID = 1023 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1043 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1065 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 1072 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1080 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 1130 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 46 where itemID = 1189 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 1200 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1224 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1232 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 46 where itemID = 1261 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1277 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 46 where itemID = 1297 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1333 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 1344 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 69 where itemID = 1344 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 35 where itemID = 1354 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 35 where itemID = 1354 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 46 where itemID = 1367 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1383 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1388 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1392 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1426 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 36 where itemID = 1434 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 1494 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1499 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 1531 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 1531 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1539 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1552 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 38 where itemID = 1619 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 1620 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 55 where itemID = 1623 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 41 where itemID = 1630 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1668 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 54 where itemID = 1675 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 38 where itemID = 1689 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1699 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1700 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1701 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1702 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1703 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1740 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 1755 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 1850 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 26 where itemID = 1855 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 1856 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 38 where itemID = 1878 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 1882 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 1886 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 38 where itemID = 1901 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 45 where itemID = 1930 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 1933 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 56 where itemID = 1937 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 1940 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 1945 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 1945 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 30 where itemID = 2080 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 2241 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2248 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2249 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2250 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2252 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 31 where itemID = 2260 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2261 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2270 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 2297 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2304 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2305 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2306 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 41 where itemID = 2307 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2326 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2330 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2330 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 2331 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2334 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 33 where itemID = 2351 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 71 where itemID = 2373 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 31 where itemID = 2403 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2404 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2430 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2433 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 2435 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 2436 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 22 where itemID = 2437 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2438 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2458 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2460 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2465 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2467 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2467 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 2468 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 2477 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 31 where itemID = 2482 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2484 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 70 where itemID = 2486 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2521 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2525 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2540 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 3 where itemID = 2542 and transactionTypeID = 3 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 41 where itemID = 2552 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 39 where itemID = 2553 and transactionTypeID = 4 and categoryID = 19;
-update TRANSACTIONSTABLE set categoryID = 71 where itemID = 2554 and transactionTypeID = 3 and categoryID = 19;
@@ -0,0 +1,27 @@
+//! Any backup text through both parsers and, when it parses, every migration.
+//!
+//! Run with `cargo +nightly fuzz run parse_backup` from the repository root.
+#![no_main]
+
+use financisto2bluecoins::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let lines = text.split('\n').map(|l| l.to_owned()).collect::<Vec<_>>();
+    let _ = convert_maps(&lines);
+
+    let Ok(data) = parse_backup(text) else {
+        return;
+    };
+    for integrity in [Integrity::Report, Integrity::Repair, Integrity::Strict] {
+        let options = MigrateOptions {
+            integrity,
+            ..Default::default()
+        };
+        let _ = migrate_accounts(&data, &options);
+        let _ = migrate_categories(&data, &options);
+        if let Ok(statements) = migrate_transactions(&data, &options) {
+            let _ = verify_sql(&statements);
+        }
+    }
+});
@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use flate2::bufread::GzDecoder;
+use log::*;
+
+use crate::{
+    is_field, Financisto, FinancistoAccount, FinancistoCategory, FinancistoCurrency,
+    FinancistoLocation, FinancistoPayee, FinancistoProject, FinancistoTransaction, RawEntity,
+    Result,
+};
+
+/// `name:value` fields of an entity, whatever holds them.
+pub trait Fields {
+    fn field(&self, name: &str) -> Option<&str>;
+
+    /// Kind of the entity, for error messages.
+    fn entity(&self) -> &str {
+        self.field("entity").unwrap_or_default()
+    }
+
+    /// Value of a field that must be there, parsed.
+    fn required<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        let value = self.field(name).ok_or_else(|| {
+            format!(
+                "{} {}: missing field {}",
+                self.entity(),
+                self.field("_id").unwrap_or("?"),
+                name
+            )
+        })?;
+        value.parse().map_err(|e| {
+            format!(
+                "{} {}: invalid {} '{}': {}",
+                self.entity(),
+                self.field("_id").unwrap_or("?"),
+                name,
+                value,
+                e
+            )
+            .into()
+        })
+    }
+
+    /// Value of a text field that must be there.
+    fn text(&self, name: &str) -> Result<String> {
+        self.required(name)
+    }
+
+    /// Value of a field Financisto leaves out when it is NULL.
+    fn optional(&self, name: &str) -> Option<String> {
+        self.field(name).map(|v| v.to_owned())
+    }
+}
+
+impl Fields for HashMap<String, String> {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.get(name).map(|v| v.as_str())
+    }
+}
+
+/// An entity model built from its fields.
+pub trait FromFields: Sized {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self>;
+}
+
+/// Fields of one entity borrowed from the backup text, only multi-line values are copied.
+#[derive(Debug, Default)]
+struct EntityFields<'a> {
+    fields: Vec<(&'a str, Cow<'a, str>)>,
+}
+
+impl Fields for EntityFields<'_> {
+    /// The last value, as a field written twice overwrites the first one.
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_ref())
+    }
+}
+
+impl EntityFields<'_> {
+    fn to_raw(&self) -> RawEntity {
+        self.fields
+            .iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>()
+            .into()
+    }
+}
+
+/// Parse a decompressed backup, like [`crate::convert_maps`] but straight from the text into the
+/// entity models, borrowing the values instead of copying each line into a `String`.
+///
+/// The result is the same as the one of [`crate::convert_maps`], the entities of the kinds that
+/// are not modeled are kept in [`Financisto::raw`]. Missing or malformed fields are errors rather
+/// than panics.
+pub fn parse_backup(text: &str) -> Result<Financisto> {
+    let mut data = Financisto::new();
+    let mut lines = text
+        .split('\n')
+        .map(|l| l.trim_end_matches('\r'))
+        .enumerate()
+        .peekable();
+
+    // the 4 header lines, as in `convert_maps`
+    lines.nth(3);
+
+    let mut fields = EntityFields::default();
+    while let Some((index, line)) = lines.next() {
+        let Some(entity) = line.strip_prefix("$ENTITY:") else {
+            if !line.is_empty() && "#START" != line && "#END" != line {
+                warn!("line {} is outside of any entity: {}", index + 1, line);
+            }
+            continue;
+        };
+
+        fields.fields.clear();
+        fields.fields.push(("entity", Cow::Borrowed(entity)));
+        while let Some(&(index, line)) = lines.peek() {
+            if line.starts_with("$ENTITY:") {
+                // the `$$` of this entity is missing
+                break;
+            }
+            lines.next();
+
+            // see `is_entity_end`
+            if line == "$$"
+                && lines
+                    .peek()
+                    .is_none_or(|(_, next)| *next == "#END" || next.starts_with("$ENTITY:"))
+            {
+                break;
+            }
+
+            match line.split_once(':').filter(|_| is_field(line)) {
+                Some((name, value)) => fields.fields.push((name, Cow::Borrowed(value))),
+                None => match fields.fields.last_mut() {
+                    Some((_, value)) => {
+                        let value = value.to_mut();
+                        value.push('\n');
+                        value.push_str(line);
+                    }
+                    None => warn!("line {} is not a field: {}", index + 1, line),
+                },
+            }
+        }
+
+        // a later `entity` field overwrites the kind, as in the map of `convert_maps`
+        let entity = fields.entity();
+        match entity {
+            "currency" => data
+                .currencies
+                .push(FinancistoCurrency::from_fields(&fields)?),
+            "account" => data.accounts.push(FinancistoAccount::from_fields(&fields)?),
+            "category" => data
+                .categories
+                .push(FinancistoCategory::from_fields(&fields)?),
+            "payee" => data.payees.push(FinancistoPayee::from_fields(&fields)?),
+            "project" => data.projects.push(FinancistoProject::from_fields(&fields)?),
+            "locations" => data
+                .locations
+                .push(FinancistoLocation::from_fields(&fields)?),
+            "transactions" => data
+                .transactions
+                .push(FinancistoTransaction::from_fields(&fields)?),
+            _ => {
+                // only the kinds that are not modeled are copied out of the text
+                data.raw.push(fields.to_raw());
+                continue;
+            }
+        }
+        data.raw
+            .read(entity, fields.fields.iter().map(|(name, _)| *name));
+    }
+
+    Ok(data)
+}
+
+/// Read a gzipped Financisto backup file (`*.backup`), see [`parse_backup`].
+pub fn read_backup<P: AsRef<Path>>(path: P) -> Result<Financisto> {
+    let file = fs::File::open(path)?;
+    let mut decoded = GzDecoder::new(BufReader::new(file));
+    let mut content = String::new();
+    decoded.read_to_string(&mut content)?;
+
+    parse_backup(&content)
+}
@@ -0,0 +1,105 @@
+use crate::escape_quote;
+
+/// `transactionTypeID` of an expense.
+pub const TYPE_EXPENSE: i32 = 3;
+/// `transactionTypeID` of an income.
+pub const TYPE_INCOME: i32 = 4;
+/// `transactionTypeID` of both legs of a transfer.
+pub const TYPE_TRANSFER: i32 = 5;
+
+/// `reminderFrequency` of a reminder that fires once.
+pub const REMINDER_ONCE: i32 = 0;
+pub const REMINDER_DAILY: i32 = 1;
+pub const REMINDER_WEEKLY: i32 = 2;
+pub const REMINDER_MONTHLY: i32 = 3;
+pub const REMINDER_YEARLY: i32 = 4;
+
+/// `reminderEndingType` of a reminder that repeats forever.
+pub const REMINDER_ENDS_NEVER: i32 = 0;
+/// `reminderEndingType` of a reminder that stops at `reminderEndDate`.
+pub const REMINDER_ENDS_ON_DATE: i32 = 1;
+/// `reminderEndingType` of a reminder that stops after `reminderAfterNoOfOccurences`.
+pub const REMINDER_ENDS_AFTER: i32 = 2;
+
+/// A row of Bluecoins' TRANSACTIONSTABLE.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BluecoinsTransaction {
+    pub transactions_table_id: i64,
+    pub item_id: i64,
+    /// Amount with 6 decimal digits.
+    pub amount: i64,
+    pub transaction_currency: String,
+    pub conversion_rate_new: f64,
+    pub date: String,
+    pub transaction_type_id: i32,
+    pub category_id: i32,
+    pub account_id: i32,
+    pub notes: String,
+    pub status: i32,
+    pub account_reference: i32,
+    pub account_pair_id: i32,
+    pub uid_pair_id: i64,
+    pub deleted_transaction: i32,
+    pub new_split_transaction_id: i64,
+    pub transfer_group_id: i64,
+    pub reminder: Option<BluecoinsReminder>,
+}
+
+/// Reminder columns of a TRANSACTIONSTABLE row, for scheduled transactions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BluecoinsReminder {
+    pub group_id: i64,
+    pub frequency: i32,
+    pub repeat_every: i32,
+    pub ending_type: i32,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub after_occurrences: Option<i32>,
+}
+
+fn nullable<T: ToString>(value: Option<T>) -> String {
+    match value {
+        Some(value) => format!("'{}'", escape_quote(&value.to_string())),
+        None => "NULL".to_owned(),
+    }
+}
+
+impl BluecoinsTransaction {
+    pub fn to_sql(&self) -> String {
+        let reminder = match &self.reminder {
+            Some(reminder) => format!(
+                "'1', '{reminderGroupID}', '{reminderFrequency}', '{reminderRepeatEvery}', '{reminderEndingType}', '{reminderStartDate}', {reminderEndDate}, {reminderAfterNoOfOccurences}, '0', '0', '0', '0', '0', '0', '1'",
+                reminderGroupID = reminder.group_id,
+                reminderFrequency = reminder.frequency,
+                reminderRepeatEvery = reminder.repeat_every,
+                reminderEndingType = reminder.ending_type,
+                reminderStartDate = reminder.start_date,
+                reminderEndDate = nullable(reminder.end_date.as_ref()),
+                reminderAfterNoOfOccurences = nullable(reminder.after_occurrences),
+            ),
+            None => "NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL".to_owned(),
+        };
+
+        format!(
+            "INSERT INTO \"TRANSACTIONSTABLE\" (\"transactionsTableID\", \"itemID\", \"amount\", \"transactionCurrency\", \"conversionRateNew\", \"date\", \"transactionTypeID\", \"categoryID\", \"accountID\", \"notes\", \"status\", \"accountReference\", \"accountPairID\", \"uidPairID\", \"deletedTransaction\", \"newSplitTransactionID\", \"transferGroupID\", \"reminderTransaction\", \"reminderGroupID\", \"reminderFrequency\", \"reminderRepeatEvery\", \"reminderEndingType\", \"reminderStartDate\", \"reminderEndDate\", \"reminderAfterNoOfOccurences\", \"reminderAutomaticLogTransaction\", \"reminderRepeatByDayOfMonth\", \"reminderExcludeWeekend\", \"reminderWeekDayMoveSetting\", \"reminderUnbilled\", \"creditCardInstallment\", \"reminderVersion\", \"dataExtraColumnString1\") VALUES ('{transactionsTableID}', '{itemID}', '{amount}', '{transactionCurrency}', '{conversionRateNew}', '{date}', '{transactionTypeID}', '{categoryID}', '{accountID}', '{notes}', '{status}', '{accountReference}', '{accountPairID}', '{uidPairID}', '{deletedTransaction}', '{newSplitTransactionID}', '{transferGroupID}', {reminder}, NULL);",
+            transactionsTableID = self.transactions_table_id,
+            itemID = self.item_id,
+            amount = self.amount,
+            transactionCurrency = self.transaction_currency,
+            conversionRateNew = self.conversion_rate_new,
+            date = self.date,
+            transactionTypeID = self.transaction_type_id,
+            categoryID = self.category_id,
+            accountID = self.account_id,
+            notes = escape_quote(&self.notes),
+            status = self.status,
+            accountReference = self.account_reference,
+            accountPairID = self.account_pair_id,
+            uidPairID = self.uid_pair_id,
+            deletedTransaction = self.deleted_transaction,
+            newSplitTransactionID = self.new_split_transaction_id,
+            transferGroupID = self.transfer_group_id,
+            reminder = reminder,
+        )
+    }
+}
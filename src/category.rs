@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::FinancistoCategory;
+
+/// How a Financisto category tree of any depth is flattened into Bluecoins' two-level model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CategoryFlatten {
+    /// Every category with children becomes a Bluecoins parent, and each category is placed under
+    /// its nearest ancestor.
+    #[default]
+    NearestParent,
+    /// Only top-level categories become Bluecoins parents, every descendant is placed under its root.
+    TopLevel,
+    /// Like `TopLevel`, but descendants are named by their path below the root, e.g. `Parent:Child`.
+    Joined,
+}
+
+/// Category tree rebuilt from the nested set (`left`/`right`) stored by Financisto.
+pub struct CategoryTree<'a> {
+    nodes: Vec<&'a FinancistoCategory>,
+    index: HashMap<i32, usize>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+}
+
+impl<'a> CategoryTree<'a> {
+    pub fn new(categories: &'a [FinancistoCategory]) -> Self {
+        let mut nodes = categories.iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|c| (c.left, -c.right));
+
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.id, i))
+            .collect::<HashMap<_, _>>();
+        let mut parents = vec![None; nodes.len()];
+        let mut children = vec![Vec::new(); nodes.len()];
+
+        // walk in `left` order, keeping the chain of currently open ancestors
+        let mut stack: Vec<usize> = Vec::new();
+        for (i, node) in nodes.iter().enumerate() {
+            while let Some(&top) = stack.last() {
+                if nodes[top].left < node.left && node.right < nodes[top].right {
+                    break;
+                }
+                stack.pop();
+            }
+            if let Some(&top) = stack.last() {
+                parents[i] = Some(top);
+                children[top].push(i);
+            }
+            stack.push(i);
+        }
+
+        Self {
+            nodes,
+            index,
+            parents,
+            children,
+        }
+    }
+
+    pub fn get(&self, id: i32) -> Option<&'a FinancistoCategory> {
+        self.index.get(&id).map(|&i| self.nodes[i])
+    }
+
+    /// All categories in tree (pre-)order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a FinancistoCategory> + '_ {
+        self.nodes.iter().copied()
+    }
+
+    pub fn roots(&self) -> Vec<&'a FinancistoCategory> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.parents[*i].is_none())
+            .map(|(_, c)| *c)
+            .collect()
+    }
+
+    pub fn parent(&self, id: i32) -> Option<&'a FinancistoCategory> {
+        let i = *self.index.get(&id)?;
+        self.parents[i].map(|p| self.nodes[p])
+    }
+
+    pub fn children(&self, id: i32) -> Vec<&'a FinancistoCategory> {
+        self.index
+            .get(&id)
+            .map(|&i| self.children[i].iter().map(|&c| self.nodes[c]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn has_children(&self, id: i32) -> bool {
+        self.index
+            .get(&id)
+            .map(|&i| !self.children[i].is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Path from the root down to (and including) the category.
+    pub fn path(&self, id: i32) -> Vec<&'a FinancistoCategory> {
+        let mut path = Vec::new();
+        let mut current = self.index.get(&id).copied();
+        while let Some(i) = current {
+            path.push(self.nodes[i]);
+            current = self.parents[i];
+        }
+        path.reverse();
+        path
+    }
+
+    pub fn root(&self, id: i32) -> Option<&'a FinancistoCategory> {
+        self.path(id).first().copied()
+    }
+
+    /// Zero for top-level categories.
+    pub fn depth(&self, id: i32) -> usize {
+        self.path(id).len().saturating_sub(1)
+    }
+
+    pub fn flatten(&self, strategy: CategoryFlatten) -> FlatCategories<'a> {
+        let mut flat = FlatCategories {
+            parents: Vec::new(),
+            children: Vec::new(),
+        };
+
+        for node in self.iter() {
+            let is_root = self.parent(node.id).is_none();
+            let is_parent = match strategy {
+                CategoryFlatten::NearestParent => is_root || self.has_children(node.id),
+                CategoryFlatten::TopLevel | CategoryFlatten::Joined => is_root,
+            };
+
+            if is_parent {
+                flat.parents.push(node);
+            }
+
+            let parent = if is_parent {
+                node
+            } else {
+                match strategy {
+                    CategoryFlatten::NearestParent => self.parent(node.id).unwrap(),
+                    CategoryFlatten::TopLevel | CategoryFlatten::Joined => {
+                        self.root(node.id).unwrap()
+                    }
+                }
+            };
+
+            let name = match strategy {
+                CategoryFlatten::Joined if !is_parent => self
+                    .path(node.id)
+                    .iter()
+                    .skip(1)
+                    .map(|c| c.title.as_str())
+                    .collect::<Vec<_>>()
+                    .join(":"),
+                _ => node.title.to_owned(),
+            };
+
+            flat.children.push(FlatCategory {
+                category: node,
+                name,
+                parent,
+            });
+        }
+
+        flat
+    }
+}
+
+/// A Bluecoins child category produced by [`CategoryTree::flatten`].
+#[derive(Debug)]
+pub struct FlatCategory<'a> {
+    pub category: &'a FinancistoCategory,
+    pub name: String,
+    pub parent: &'a FinancistoCategory,
+}
+
+#[derive(Debug)]
+pub struct FlatCategories<'a> {
+    /// Categories emitted as Bluecoins parent categories.
+    pub parents: Vec<&'a FinancistoCategory>,
+    /// Every category, each placed under one of `parents`. Parents also get a child of their own.
+    pub children: Vec<FlatCategory<'a>>,
+}
+
+/// Financisto's built-in "no category", used by uncategorized transactions and transfers.
+pub const NO_CATEGORY_ID: i32 = 0;
+/// Financisto's built-in marker category of split parents.
+pub const SPLIT_CATEGORY_ID: i32 = -1;
+
+/// Whether the category is one of Financisto's built-in markers rather than a user category.
+pub fn is_system_category(id: i32) -> bool {
+    id == NO_CATEGORY_ID || id == SPLIT_CATEGORY_ID
+}
+
+pub const UNCATEGORIZED_NAME: &str = "Uncategorized";
+
+/// Bluecoins category IDs assigned to migrated Financisto categories.
+#[derive(Debug)]
+pub struct CategoryIds {
+    migrated: HashSet<i32>,
+    /// Child (and parent) category ID of the uncategorized expense category created by the migration.
+    pub uncategorized_expense: i32,
+    /// Child (and parent) category ID of the uncategorized income category created by the migration.
+    pub uncategorized_income: i32,
+}
+
+impl CategoryIds {
+    pub fn new(categories: &[FinancistoCategory]) -> Self {
+        let migrated = categories
+            .iter()
+            .filter(|c| !is_system_category(c.id))
+            .map(|c| c.id)
+            .collect::<HashSet<_>>();
+        let last_id = migrated.iter().max().copied().unwrap_or(0) + 19;
+
+        Self {
+            migrated,
+            uncategorized_expense: last_id + 1,
+            uncategorized_income: last_id + 2,
+        }
+    }
+
+    /// Bluecoins child category ID of a Financisto category, `None` if it was not migrated.
+    pub fn resolve(&self, category_id: i32, income: bool) -> Option<i32> {
+        if is_system_category(category_id) {
+            Some(if income {
+                self.uncategorized_income
+            } else {
+                self.uncategorized_expense
+            })
+        } else if self.migrated.contains(&category_id) {
+            Some(category_id + 19)
+        } else {
+            None
+        }
+    }
+}
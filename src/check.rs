@@ -0,0 +1,334 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use log::*;
+
+use crate::{is_scheduled, is_system_category, Financisto, Result};
+
+/// A transaction flagged as credit card payment that does not pay into a credit card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditCardPaymentIssue {
+    pub transaction_id: i32,
+    pub account_id: i32,
+    /// Financisto type of the target account, `None` if the payment is not a transfer or the
+    /// account is missing.
+    pub account_type: Option<String>,
+}
+
+impl fmt::Display for CreditCardPaymentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.account_type {
+            Some(account_type) => write!(
+                f,
+                "credit card payment {} goes to account {} of type {}",
+                self.transaction_id, self.account_id, account_type
+            ),
+            None if self.account_id == 0 => write!(
+                f,
+                "credit card payment {} is not a transfer",
+                self.transaction_id
+            ),
+            None => write!(
+                f,
+                "credit card payment {} goes to unknown account {}",
+                self.transaction_id, self.account_id
+            ),
+        }
+    }
+}
+
+/// Credit card payments whose target account is not a credit card.
+pub fn check_credit_card_payments(data: &Financisto) -> Vec<CreditCardPaymentIssue> {
+    let account_types = data
+        .accounts
+        .iter()
+        .map(|a| (a.id, a.r#type.as_str()))
+        .collect::<HashMap<_, _>>();
+
+    data.transactions
+        .iter()
+        .filter(|tx| tx.is_ccard_payment == 1)
+        .filter_map(|tx| match account_types.get(&tx.to_account_id) {
+            Some(&"CREDIT_CARD") => None,
+            account_type => Some(CreditCardPaymentIssue {
+                transaction_id: tx.id,
+                account_id: tx.to_account_id,
+                account_type: account_type.map(|t| t.to_string()),
+            }),
+        })
+        .collect()
+}
+
+/// What to do with the problems found by [`check_integrity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Integrity {
+    /// Log the problems and migrate the data as it is, orphan splits are dropped. Duplicate
+    /// account, category or transaction IDs and unsupported recurrences can not be migrated as
+    /// they are and are refused.
+    #[default]
+    Report,
+    /// Log the problems and work around them: orphan splits are migrated as standalone
+    /// transactions, unknown categories become Uncategorized, and transactions on unknown accounts
+    /// or transferring into their own account, the later entities of a duplicate ID and unsupported
+    /// recurrences are skipped.
+    Repair,
+    /// Refuse to migrate data with any problem.
+    Strict,
+}
+
+/// A reference in the Financisto data that does not resolve.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityIssue {
+    /// Split line whose parent is missing, itself a split line or a template that is not scheduled,
+    /// it is never migrated.
+    OrphanSplit {
+        transaction_id: i32,
+        parent_id: i32,
+    },
+    UnknownAccount {
+        transaction_id: i32,
+        account_id: i32,
+    },
+    UnknownCategory {
+        transaction_id: i32,
+        category_id: i32,
+    },
+    UnknownPayee {
+        transaction_id: i32,
+        payee_id: i32,
+    },
+    /// `entity` is the Financisto entity referring to the currency, "account" or "transactions".
+    UnknownCurrency {
+        entity: &'static str,
+        id: i32,
+        currency_id: i32,
+    },
+    SelfTransfer {
+        transaction_id: i32,
+        account_id: i32,
+    },
+    /// Another entity of the same kind with the ID comes first, `entity` is "currency",
+    /// "account", "category", "payee" or "transactions".
+    DuplicateId {
+        entity: &'static str,
+        id: i32,
+    },
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OrphanSplit {
+                transaction_id,
+                parent_id,
+            } => write!(
+                f,
+                "split line {} refers to parent {} that is not migrated",
+                transaction_id, parent_id
+            ),
+            Self::UnknownAccount {
+                transaction_id,
+                account_id,
+            } => write!(
+                f,
+                "transaction {} refers to unknown account {}",
+                transaction_id, account_id
+            ),
+            Self::UnknownCategory {
+                transaction_id,
+                category_id,
+            } => write!(
+                f,
+                "transaction {} refers to unknown category {}",
+                transaction_id, category_id
+            ),
+            Self::UnknownPayee {
+                transaction_id,
+                payee_id,
+            } => write!(
+                f,
+                "transaction {} refers to unknown payee {}",
+                transaction_id, payee_id
+            ),
+            Self::UnknownCurrency {
+                entity,
+                id,
+                currency_id,
+            } => write!(
+                f,
+                "{} {} refers to unknown currency {}",
+                entity, id, currency_id
+            ),
+            Self::SelfTransfer {
+                transaction_id,
+                account_id,
+            } => write!(
+                f,
+                "transaction {} transfers into its own account {}",
+                transaction_id, account_id
+            ),
+            Self::DuplicateId { entity, id } => write!(f, "duplicate {} ID {}", entity, id),
+        }
+    }
+}
+
+impl IntegrityIssue {
+    /// Transaction the issue is about, `None` for accounts.
+    pub fn transaction_id(&self) -> Option<i32> {
+        match self {
+            Self::OrphanSplit { transaction_id, .. }
+            | Self::UnknownAccount { transaction_id, .. }
+            | Self::UnknownCategory { transaction_id, .. }
+            | Self::UnknownPayee { transaction_id, .. }
+            | Self::SelfTransfer { transaction_id, .. } => Some(*transaction_id),
+            Self::UnknownCurrency { entity, id, .. } | Self::DuplicateId { entity, id }
+                if *entity == "transactions" =>
+            {
+                Some(*id)
+            }
+            Self::UnknownCurrency { .. } | Self::DuplicateId { .. } => None,
+        }
+    }
+}
+
+/// Duplicate IDs, references between accounts, categories, currencies, payees and transactions
+/// that do not resolve, and transfers into the same account.
+pub fn check_integrity(data: &Financisto) -> Vec<IntegrityIssue> {
+    let index = data.index();
+    let mut issues = Vec::new();
+
+    let ids = [
+        (
+            "currency",
+            data.currencies.iter().map(|c| c.id).collect::<Vec<_>>(),
+        ),
+        ("account", data.accounts.iter().map(|a| a.id).collect()),
+        ("category", data.categories.iter().map(|c| c.id).collect()),
+        ("payee", data.payees.iter().map(|p| p.id).collect()),
+        (
+            "transactions",
+            data.transactions.iter().map(|t| t.id).collect(),
+        ),
+    ];
+    for (entity, ids) in ids {
+        let mut seen = HashSet::new();
+        for id in ids.into_iter().filter(|id| !seen.insert(*id)) {
+            issues.push(IntegrityIssue::DuplicateId { entity, id });
+        }
+    }
+
+    for account in data.accounts.iter() {
+        if index.currency(account.currency_id).is_none() {
+            issues.push(IntegrityIssue::UnknownCurrency {
+                entity: "account",
+                id: account.id,
+                currency_id: account.currency_id,
+            });
+        }
+    }
+
+    for tx in data.transactions.iter() {
+        if tx.parent_id != 0
+            && index
+                .transaction(tx.parent_id)
+                .is_none_or(|p| p.parent_id != 0 || (p.is_template != 0 && !is_scheduled(p)))
+        {
+            issues.push(IntegrityIssue::OrphanSplit {
+                transaction_id: tx.id,
+                parent_id: tx.parent_id,
+            });
+        }
+
+        for account_id in [tx.from_account_id, tx.to_account_id] {
+            if account_id != 0 && index.account(account_id).is_none() {
+                issues.push(IntegrityIssue::UnknownAccount {
+                    transaction_id: tx.id,
+                    account_id,
+                });
+            }
+        }
+
+        // categories of transfers are not migrated
+        if tx.to_account_id == 0
+            && !is_system_category(tx.category_id)
+            && index.category(tx.category_id).is_none()
+        {
+            issues.push(IntegrityIssue::UnknownCategory {
+                transaction_id: tx.id,
+                category_id: tx.category_id,
+            });
+        }
+
+        if tx.payee_id != 0 && index.payee(tx.payee_id).is_none() {
+            issues.push(IntegrityIssue::UnknownPayee {
+                transaction_id: tx.id,
+                payee_id: tx.payee_id,
+            });
+        }
+
+        // 0 means the currency of the account
+        if tx.original_currency_id != 0 && index.currency(tx.original_currency_id).is_none() {
+            issues.push(IntegrityIssue::UnknownCurrency {
+                entity: "transactions",
+                id: tx.id,
+                currency_id: tx.original_currency_id,
+            });
+        }
+
+        if tx.to_account_id != 0 && tx.to_account_id == tx.from_account_id {
+            issues.push(IntegrityIssue::SelfTransfer {
+                transaction_id: tx.id,
+                account_id: tx.from_account_id,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Orphan splits to migrate as standalone transactions, and transactions to leave out, when the
+/// issues are repaired.
+pub(crate) fn repairs(
+    issues: &[IntegrityIssue],
+    integrity: Integrity,
+) -> (HashSet<i32>, HashSet<i32>) {
+    let mut orphans = HashSet::new();
+    let mut skipped = HashSet::new();
+    if integrity == Integrity::Repair {
+        for issue in issues.iter() {
+            match issue {
+                IntegrityIssue::OrphanSplit { transaction_id, .. } => {
+                    orphans.insert(*transaction_id);
+                }
+                IntegrityIssue::UnknownAccount { transaction_id, .. }
+                | IntegrityIssue::SelfTransfer { transaction_id, .. } => {
+                    skipped.insert(*transaction_id);
+                }
+                _ => {}
+            }
+        }
+    }
+    (orphans, skipped)
+}
+
+/// The entities but the later ones of a duplicate ID when the issues are repaired, otherwise an
+/// error for the first duplicate ID.
+pub(crate) fn first_of_ids<'a, T>(
+    entity: &str,
+    entities: &'a [T],
+    id: impl Fn(&T) -> i32,
+    integrity: Integrity,
+) -> Result<Vec<&'a T>> {
+    let mut seen = HashSet::new();
+    let mut first = Vec::with_capacity(entities.len());
+    for e in entities.iter() {
+        if seen.insert(id(e)) {
+            first.push(e);
+        } else if integrity == Integrity::Repair {
+            warn!("skip duplicate {} ID {}", entity, id(e));
+        } else {
+            return Err(format!("duplicate {} ID {}", entity, id(e)).into());
+        }
+    }
+    Ok(first)
+}
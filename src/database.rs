@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use log::*;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::{Financisto, FromFields, RawEntities, Result, MODELED_KINDS};
+
+/// Read Financisto's own SQLite database (`financisto.db`) into the same entities as a backup.
+///
+/// Unlike a backup, every table is kept in [`Financisto::raw`], the modeled ones are also
+/// converted.
+pub fn read_database<P: AsRef<Path>>(path: P) -> Result<Financisto> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut data = Financisto::new();
+
+    let mut statement = connection.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' \
+         AND name NOT LIKE 'sqlite_%' AND name <> 'android_metadata' ORDER BY name",
+    )?;
+    let tables = statement
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for table in tables.iter() {
+        for map in read_table(&connection, table)? {
+            data.raw.push(map.into());
+        }
+    }
+
+    for table in MODELED_KINDS {
+        if !tables.iter().any(|t| t == table) {
+            warn!("no {} table in the database", table);
+        }
+    }
+    let raw = &data.raw;
+    data.currencies = models(raw, "currency")?;
+    data.accounts = models(raw, "account")?;
+    data.categories = models(raw, "category")?;
+    data.payees = models(raw, "payee")?;
+    data.projects = models(raw, "project")?;
+    data.locations = models(raw, "locations")?;
+    data.transactions = models(raw, "transactions")?;
+
+    Ok(data)
+}
+
+/// Entities of a table converted into their model.
+fn models<T: FromFields>(raw: &RawEntities, table: &str) -> Result<Vec<T>> {
+    raw.of_type(table)
+        .iter()
+        .map(|e| T::from_fields(&e.fields))
+        .collect()
+}
+
+/// Rows of a table as backup entities: the table is the entity, NULL columns are left out.
+fn read_table(connection: &Connection, table: &str) -> Result<Vec<HashMap<String, String>>> {
+    let mut statement = connection.prepare(&format!("SELECT * FROM \"{}\"", table))?;
+    let columns = statement
+        .column_names()
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>();
+    let mut rows = statement.query([])?;
+    let mut list = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let mut map = HashMap::new();
+        map.insert("entity".to_owned(), table.to_owned());
+        for (index, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(index)? {
+                ValueRef::Null | ValueRef::Blob(_) => continue,
+                ValueRef::Integer(value) => value.to_string(),
+                ValueRef::Real(value) => value.to_string(),
+                ValueRef::Text(value) => String::from_utf8_lossy(value).into_owned(),
+            };
+            map.insert(column.to_owned(), value);
+        }
+        trace!("{} = {:?}", table, &map);
+        list.push(map);
+    }
+
+    Ok(list)
+}
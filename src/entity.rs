@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An entity as it was read, `name:value` fields by column, also for kinds the crate does not
+/// model.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(transparent))]
+pub struct RawEntity {
+    /// Every field, including `entity`.
+    pub fields: HashMap<String, String>,
+}
+
+impl From<HashMap<String, String>> for RawEntity {
+    fn from(fields: HashMap<String, String>) -> Self {
+        Self { fields }
+    }
+}
+
+impl RawEntity {
+    /// Kind of the entity, the Financisto table it comes from.
+    pub fn entity(&self) -> &str {
+        self.get("entity").unwrap_or_default()
+    }
+
+    /// The `_id` field, `None` if it is missing or not a number.
+    pub fn id(&self) -> Option<i64> {
+        self.get("_id").and_then(|id| id.parse().ok())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(|v| v.as_str())
+    }
+}
+
+/// Kinds of entities converted into the Financisto model.
+pub(crate) const MODELED_KINDS: [&str; 7] = [
+    "currency",
+    "account",
+    "category",
+    "payee",
+    "project",
+    "locations",
+    "transactions",
+];
+
+/// What was read of a kind of entity, also when its entities are not kept.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Kind {
+    count: usize,
+    fields: BTreeSet<String>,
+}
+
+/// Raw entities of a backup or database, grouped by kind in the order they were read.
+///
+/// A backup only keeps the entities of the kinds that are not modeled, of the others only their
+/// number and field names are recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RawEntities {
+    entities: BTreeMap<String, Vec<RawEntity>>,
+    /// Position of each entity in its group, by kind and `_id`.
+    ids: HashMap<String, HashMap<i64, usize>>,
+    kinds: BTreeMap<String, Kind>,
+}
+
+impl RawEntities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, entity: RawEntity) {
+        self.read(entity.entity(), entity.fields.keys().map(|k| k.as_str()));
+        let kind = entity.entity().to_owned();
+        let group = self.entities.entry(kind.to_owned()).or_default();
+        if let Some(id) = entity.id() {
+            self.ids.entry(kind).or_default().insert(id, group.len());
+        }
+        group.push(entity);
+    }
+
+    /// Record an entity that is not kept, by its kind and the names of its fields.
+    pub fn read<'a>(&mut self, entity: &str, fields: impl IntoIterator<Item = &'a str>) {
+        if !self.kinds.contains_key(entity) {
+            self.kinds.insert(entity.to_owned(), Kind::default());
+        }
+        let kind = self.kinds.get_mut(entity).unwrap();
+        kind.count += 1;
+        for field in fields {
+            if !kind.fields.contains(field) {
+                kind.fields.insert(field.to_owned());
+            }
+        }
+    }
+
+    /// Kinds of entities read, in alphabetical order, also the ones not kept.
+    pub fn types(&self) -> impl Iterator<Item = &str> {
+        self.kinds.keys().map(|k| k.as_str())
+    }
+
+    /// Number of entities of a kind read, also the ones not kept.
+    pub fn count(&self, entity: &str) -> usize {
+        self.kinds.get(entity).map(|k| k.count).unwrap_or_default()
+    }
+
+    /// Entities of one kind, empty if there is none.
+    pub fn of_type(&self, entity: &str) -> &[RawEntity] {
+        self.entities
+            .get(entity)
+            .map(|list| list.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// All entities, grouped by kind.
+    pub fn iter(&self) -> impl Iterator<Item = &RawEntity> {
+        self.entities.values().flatten()
+    }
+
+    /// The entity of a kind with the given `_id`, the last one if the ID is used twice.
+    pub fn get(&self, entity: &str, id: i64) -> Option<&RawEntity> {
+        let index = self.ids.get(entity)?.get(&id)?;
+        self.entities.get(entity)?.get(*index)
+    }
+
+    /// Names of the fields seen in the entities of a kind, also the ones not kept.
+    pub fn fields(&self, entity: &str) -> BTreeSet<&str> {
+        self.kinds
+            .get(entity)
+            .map(|k| k.fields.iter().map(|f| f.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of entities kept.
+    pub fn len(&self) -> usize {
+        self.entities.values().map(|list| list.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A flat list of the field maps, the grouping and the index are rebuilt when loading.
+#[cfg(feature = "serialize")]
+impl Serialize for RawEntities {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> Deserialize<'de> for RawEntities {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut entities = Self::new();
+        for entity in Vec::<RawEntity>::deserialize(deserializer)? {
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
@@ -0,0 +1,226 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Days, NaiveDate};
+
+use crate::rules::{category_titles, lookup_title, titles};
+use crate::{
+    is_system_category, CategoryTree, Financisto, FinancistoTransaction, Result, Timezone,
+    NO_CATEGORY_ID, SPLIT_CATEGORY_ID,
+};
+
+/// Accounts or categories picked by ID or title, categories also by "Parent:Child" path.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selection {
+    /// Only these are migrated, everything if empty.
+    pub include: Vec<String>,
+    /// Never migrated, even if included.
+    pub exclude: Vec<String>,
+}
+
+impl Selection {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    fn resolve(
+        &self,
+        kind: &str,
+        lookup: &HashMap<String, Vec<i32>>,
+    ) -> Result<(Vec<i32>, Vec<i32>)> {
+        let ids = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| {
+                    // IDs win over titles that happen to be numbers
+                    match name.parse::<i32>() {
+                        Ok(id) if lookup.values().any(|ids| ids.contains(&id)) => Ok(id),
+                        _ => Ok(lookup_title(kind, lookup, &Some(name.to_owned()))?.unwrap()),
+                    }
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok((ids(&self.include)?, ids(&self.exclude)?))
+    }
+}
+
+/// Limits a migration to part of the Financisto data.
+///
+/// Transactions are kept when they fall in the date range, touch a selected account and, unless
+/// they are transfers, have a selected category or a selected parent category. Split lines are
+/// filtered one by one. A transfer with one side outside the selected accounts becomes an income
+/// or expense of the other side.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    /// First day migrated, in the timezone of the migration.
+    pub from: Option<NaiveDate>,
+    /// Last day migrated, in the timezone of the migration.
+    pub to: Option<NaiveDate>,
+    pub accounts: Selection,
+    pub categories: Selection,
+}
+
+impl Filter {
+    pub fn is_empty(&self) -> bool {
+        self.from.is_none()
+            && self.to.is_none()
+            && self.accounts.is_empty()
+            && self.categories.is_empty()
+    }
+
+    /// Resolve the titles used by the filter against the Financisto data, and its dates in the
+    /// timezone.
+    pub fn resolve(&self, data: &Financisto, timezone: Timezone) -> Result<FilterSet> {
+        let (include, exclude) = self.accounts.resolve(
+            "account",
+            &titles(
+                data.accounts
+                    .iter()
+                    .flat_map(|a| [(a.title.to_owned(), a.id), (a.id.to_string(), a.id)]),
+            ),
+        )?;
+        let accounts = if self.accounts.is_empty() {
+            None
+        } else {
+            Some(
+                data.accounts
+                    .iter()
+                    .map(|a| a.id)
+                    .filter(|id| include.is_empty() || include.contains(id))
+                    .filter(|id| !exclude.contains(id))
+                    .collect(),
+            )
+        };
+
+        let tree = CategoryTree::new(&data.categories);
+        let (include, exclude) = self
+            .categories
+            .resolve("category", &category_titles(&tree))?;
+        // a category is picked along with its parents
+        let picked = |id: i32, picks: &[i32]| tree.path(id).iter().any(|c| picks.contains(&c.id));
+        let categories = data
+            .categories
+            .iter()
+            .map(|c| c.id)
+            .filter(|id| {
+                if is_system_category(*id) {
+                    include.is_empty() || include.contains(id)
+                } else {
+                    include.is_empty() || picked(*id, &include)
+                }
+            })
+            .filter(|id| !exclude.contains(id) && !picked(*id, &exclude))
+            .collect::<HashSet<_>>();
+        let categories = if self.categories.is_empty() {
+            None
+        } else {
+            Some(categories)
+        };
+
+        let midnight = |date: NaiveDate| {
+            timezone
+                .midnight(date)
+                .ok_or_else(|| format!("invalid date {}", date))
+        };
+
+        Ok(FilterSet {
+            accounts,
+            categories,
+            uncategorized: include.is_empty() && !exclude.contains(&NO_CATEGORY_ID),
+            from: self.from.map(midnight).transpose()?,
+            until: match self.to {
+                Some(to) => {
+                    let next = to
+                        .checked_add_days(Days::new(1))
+                        .ok_or_else(|| format!("date out of range {}", to))?;
+                    Some(midnight(next)?)
+                }
+                None => None,
+            },
+        })
+    }
+}
+
+/// A [`Filter`] with its titles resolved to Financisto IDs.
+#[derive(Debug, Clone)]
+pub struct FilterSet {
+    /// `None` keeps every account, including unknown ones.
+    accounts: Option<HashSet<i32>>,
+    /// `None` keeps every category, including unknown ones.
+    categories: Option<HashSet<i32>>,
+    /// Whether transactions without a known category are kept.
+    uncategorized: bool,
+    /// Inclusive, milliseconds.
+    from: Option<i64>,
+    /// Exclusive, milliseconds.
+    until: Option<i64>,
+}
+
+impl FilterSet {
+    pub fn account(&self, account_id: i32) -> bool {
+        self.accounts
+            .as_ref()
+            .is_none_or(|accounts| accounts.contains(&account_id))
+    }
+
+    pub fn category(&self, category_id: i32) -> bool {
+        match &self.categories {
+            None => true,
+            Some(_) if category_id == SPLIT_CATEGORY_ID => true,
+            Some(_) if is_system_category(category_id) => self.uncategorized,
+            Some(categories) => categories.contains(&category_id),
+        }
+    }
+
+    /// Whether the time falls in the date range.
+    pub fn date(&self, millis: i64) -> bool {
+        self.from.is_none_or(|from| millis >= from) && self.until.is_none_or(|until| millis < until)
+    }
+
+    /// Whether the time falls before the date range.
+    pub fn before(&self, millis: i64) -> bool {
+        self.from.is_some_and(|from| millis < from)
+    }
+
+    /// Whether the transaction is migrated, on its own fields only. Templates are not limited by
+    /// the date range, split lines are also checked with [`FilterSet::split_line`].
+    pub fn transaction(&self, tx: &FinancistoTransaction) -> bool {
+        (tx.is_template != 0 || self.date(tx.datetime))
+            && (self.account(tx.from_account_id)
+                || (tx.to_account_id != 0 && self.account(tx.to_account_id)))
+            && (tx.to_account_id != 0 || self.category(tx.category_id))
+    }
+
+    /// Whether a line of a migrated split is migrated.
+    pub fn split_line(&self, line: &FinancistoTransaction) -> bool {
+        line.to_account_id != 0 || self.category(line.category_id)
+    }
+
+    /// The transaction as seen from the selected accounts: a transfer with one side filtered out
+    /// becomes an uncategorized income or expense of the other side.
+    pub fn detach<'a>(&self, tx: &'a FinancistoTransaction) -> Cow<'a, FinancistoTransaction> {
+        if tx.to_account_id == 0 {
+            Cow::Borrowed(tx)
+        } else if !self.account(tx.from_account_id) {
+            Cow::Owned(FinancistoTransaction {
+                from_account_id: tx.to_account_id,
+                from_amount: tx.to_amount,
+                original_from_amount: 0,
+                to_account_id: 0,
+                to_amount: 0,
+                category_id: NO_CATEGORY_ID,
+                ..tx.clone()
+            })
+        } else if !self.account(tx.to_account_id) {
+            Cow::Owned(FinancistoTransaction {
+                to_account_id: 0,
+                to_amount: 0,
+                category_id: NO_CATEGORY_ID,
+                ..tx.clone()
+            })
+        } else {
+            Cow::Borrowed(tx)
+        }
+    }
+}
@@ -0,0 +1,652 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use log::*;
+
+use crate::{
+    Financisto, FinancistoAccount, FinancistoCategory, FinancistoCurrency, FinancistoPayee,
+    FinancistoTransaction, Result, Timezone, NO_CATEGORY_ID, SPLIT_CATEGORY_ID,
+};
+
+/// Date formats tried when [`ImportOptions::date_format`] is not set, Financisto's defaults.
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%d/%m/%Y", "%d.%m.%Y"];
+
+/// Settings of the CSV and QIF importers.
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Timezone the exported dates were written in.
+    pub timezone: Timezone,
+    /// Currency of the accounts when the export does not tell.
+    pub currency: String,
+    /// `chrono` format of the exported dates, Financisto's defaults are tried if `None`.
+    pub date_format: Option<String>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            timezone: Timezone::default(),
+            currency: "TWD".to_owned(),
+            date_format: None,
+        }
+    }
+}
+
+impl ImportOptions {
+    fn date(&self, text: &str) -> Result<NaiveDate> {
+        let text = text.trim();
+        match &self.date_format {
+            Some(format) => Ok(NaiveDate::parse_from_str(text, format)?),
+            None => DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+                .ok_or_else(|| format!("unknown date format '{}'", text).into()),
+        }
+    }
+
+    fn millis(&self, date: NaiveDate, time: NaiveTime) -> Result<i64> {
+        self.timezone
+            .millis(&NaiveDateTime::new(date, time))
+            .ok_or_else(|| format!("invalid time {} {}", date, time).into())
+    }
+}
+
+/// Amount in cents of an exported amount like `-1,234.50`: commas only between groups of three
+/// digits, and at most two digits after the point.
+fn cents(text: &str) -> Result<i64> {
+    let text = text.trim();
+    let invalid = || format!("invalid amount '{}'", text);
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (units, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    let groups = units.split(',').collect::<Vec<_>>();
+    let grouped = groups.len() == 1
+        || (!groups[0].is_empty()
+            && groups[0].len() <= 3
+            && groups[1..].iter().all(|g| g.len() == 3));
+    let is_digits = |text: &str| text.bytes().all(|b| b.is_ascii_digit());
+    if !grouped
+        || !groups.iter().all(|g| is_digits(g))
+        || !is_digits(fraction)
+        || fraction.len() > 2
+        || (units.is_empty() && fraction.is_empty())
+    {
+        return Err(invalid().into());
+    }
+
+    let units = match groups.concat().as_str() {
+        "" => 0,
+        units => units.parse::<i64>().map_err(|_| invalid())?,
+    };
+    let fraction = format!("{:0<2}", fraction).parse::<i64>()?;
+    let cents = units
+        .checked_mul(100)
+        .and_then(|c| c.checked_add(fraction))
+        .ok_or_else(invalid)?;
+    Ok(if negative { -cents } else { cents })
+}
+
+/// The account of a `[Account]` category, which marks a transfer.
+fn transfer_target(category: &str) -> Option<&str> {
+    category
+        .trim()
+        .strip_prefix('[')
+        .and_then(|c| c.strip_suffix(']'))
+}
+
+/// Both sides of a transfer: from account, to account, time and amount.
+type TransferKey = (i32, i32, i64, i64);
+
+/// Builds Financisto entities out of the titles found in an export.
+struct Builder<'a> {
+    options: &'a ImportOptions,
+    data: Financisto,
+    currencies: HashMap<String, i32>,
+    accounts: HashMap<String, i32>,
+    categories: HashMap<String, i32>,
+    /// Parent of each category, `None` for top-level ones.
+    parents: Vec<(i32, Option<i32>)>,
+    payees: HashMap<String, i32>,
+    /// Transfers seen from one side only: transaction, account it was listed in, and whether it is
+    /// a split line.
+    transfers: HashMap<TransferKey, Vec<(i32, i32, bool)>>,
+    removed: HashSet<i32>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(options: &'a ImportOptions) -> Self {
+        Self {
+            options,
+            data: Financisto::new(),
+            currencies: HashMap::new(),
+            accounts: HashMap::new(),
+            categories: HashMap::new(),
+            parents: Vec::new(),
+            payees: HashMap::new(),
+            transfers: HashMap::new(),
+            removed: HashSet::new(),
+        }
+    }
+
+    fn currency(&mut self, name: &str) -> i32 {
+        let name = if name.trim().is_empty() {
+            self.options.currency.as_str()
+        } else {
+            name.trim()
+        };
+        if let Some(id) = self.currencies.get(name) {
+            return *id;
+        }
+
+        let id = self.currencies.len() as i32 + 1;
+        self.currencies.insert(name.to_owned(), id);
+        self.data.currencies.push(FinancistoCurrency {
+            id,
+            decimal_separator: None,
+            updated_on: 0,
+            title: name.to_owned(),
+            entity: "currency".to_owned(),
+            decimals: 2,
+            symbol: name.to_owned(),
+            is_active: 1,
+            name: name.to_owned(),
+            is_default: (id == 1) as i32,
+            symbol_format: "RS".to_owned(),
+            group_separator: None,
+        });
+        id
+    }
+
+    fn account(&mut self, title: &str, r#type: &str, currency: &str) -> i32 {
+        let title = title.trim();
+        if let Some(id) = self.accounts.get(title) {
+            return *id;
+        }
+
+        let currency_id = self.currency(currency);
+        let id = self.accounts.len() as i32 + 1;
+        self.accounts.insert(title.to_owned(), id);
+        self.data.accounts.push(FinancistoAccount {
+            id,
+            note: None,
+            total_limit: 0,
+            entity: "account".to_owned(),
+            total_amount: 0,
+            last_transaction_date: 0,
+            payment_day: 0,
+            sort_order: id,
+            r#type: r#type.to_owned(),
+            updated_on: 0,
+            is_active: 1,
+            issuer: None,
+            title: title.to_owned(),
+            creation_date: 0,
+            last_account_id: 0,
+            last_category_id: 0,
+            currency_id,
+            closing_day: 0,
+            card_issuer: None,
+            is_include_into_totals: 1,
+        });
+        id
+    }
+
+    /// Category of a `Parent:Child` path, creating the missing levels.
+    fn category(&mut self, path: &str) -> i32 {
+        let mut parent = None;
+        let mut prefix = String::new();
+
+        for title in path.split(':').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !prefix.is_empty() {
+                prefix.push(':');
+            }
+            prefix.push_str(title);
+
+            let id = match self.categories.get(&prefix) {
+                Some(id) => *id,
+                None => {
+                    let id = self.categories.len() as i32 + 1;
+                    self.categories.insert(prefix.to_owned(), id);
+                    self.parents.push((id, parent));
+                    self.data.categories.push(FinancistoCategory {
+                        r#type: 0,
+                        id,
+                        updated_on: 0,
+                        right: 0,
+                        left: 0,
+                        last_location_id: 0,
+                        is_active: 1,
+                        title: title.to_owned(),
+                        last_project_id: 0,
+                        entity: "category".to_owned(),
+                    });
+                    id
+                }
+            };
+            parent = Some(id);
+        }
+
+        parent.unwrap_or(NO_CATEGORY_ID)
+    }
+
+    fn payee(&mut self, title: &str) -> i32 {
+        let title = title.trim();
+        if title.is_empty() {
+            return 0;
+        }
+        if let Some(id) = self.payees.get(title) {
+            return *id;
+        }
+
+        let id = self.payees.len() as i32 + 1;
+        self.payees.insert(title.to_owned(), id);
+        self.data.payees.push(FinancistoPayee {
+            id,
+            title: title.to_owned(),
+            last_category_id: 0,
+            is_active: 1,
+            updated_on: 0,
+            entity: "payee".to_owned(),
+        });
+        id
+    }
+
+    /// A new income or expense of the account.
+    fn transaction(
+        &mut self,
+        account_id: i32,
+        datetime: i64,
+        amount: i64,
+    ) -> FinancistoTransaction {
+        let currency_id = self
+            .data
+            .accounts
+            .iter()
+            .find(|a| a.id == account_id)
+            .map(|a| a.currency_id)
+            .unwrap_or_default();
+
+        FinancistoTransaction {
+            longitude: 0.0,
+            from_account_id: account_id,
+            location_id: 0,
+            note: None,
+            to_account_id: 0,
+            entity: "transactions".to_owned(),
+            datetime,
+            project_id: 0,
+            provider: None,
+            template_name: None,
+            recurrence: None,
+            latitude: 0.0,
+            accuracy: 0.0,
+            status: None,
+            is_ccard_payment: 0,
+            payee_id: 0,
+            category_id: NO_CATEGORY_ID,
+            parent_id: 0,
+            to_amount: 0,
+            from_amount: amount,
+            original_currency_id: currency_id,
+            original_from_amount: 0,
+            last_recurrence: 0,
+            is_template: 0,
+            updated_on: 0,
+            id: self.data.transactions.len() as i32 + 1,
+        }
+    }
+
+    /// Add a transaction. A transfer listed in both of its accounts is only added once, preferring
+    /// the side where it is a split line.
+    fn push(&mut self, mut tx: FinancistoTransaction, listed_in: i32) {
+        if tx.to_account_id != 0 {
+            // the money leaves the from account, but split lines stay in the account of their
+            // parent
+            let split_line = tx.parent_id != 0;
+            if tx.from_amount > 0 && !split_line {
+                std::mem::swap(&mut tx.from_account_id, &mut tx.to_account_id);
+                tx.from_amount = -tx.from_amount;
+            }
+            tx.to_amount = -tx.from_amount;
+
+            let key = if tx.from_amount > 0 {
+                (
+                    tx.to_account_id,
+                    tx.from_account_id,
+                    tx.datetime,
+                    tx.to_amount,
+                )
+            } else {
+                (
+                    tx.from_account_id,
+                    tx.to_account_id,
+                    tx.datetime,
+                    tx.from_amount,
+                )
+            };
+            let pending = self.transfers.entry(key).or_default();
+            if let Some(index) = pending
+                .iter()
+                .position(|(_, account, _)| *account != listed_in)
+            {
+                let (id, _, other_split_line) = pending.remove(index);
+                if !split_line || other_split_line {
+                    debug!("transfer {} already imported as {}", tx.id, id);
+                    return;
+                }
+                self.removed.insert(id);
+            } else {
+                pending.push((tx.id, listed_in, split_line));
+            }
+        }
+
+        self.data.transactions.push(tx);
+    }
+
+    fn finish(mut self) -> Financisto {
+        // nested set of the category hierarchy
+        let mut next = 1;
+        let roots = self
+            .parents
+            .iter()
+            .filter(|(_, parent)| parent.is_none())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for root in roots {
+            self.number(root, &mut next);
+        }
+        let mut categories = vec![FinancistoCategory {
+            r#type: 0,
+            id: NO_CATEGORY_ID,
+            updated_on: 0,
+            right: 0,
+            left: 0,
+            last_location_id: 0,
+            is_active: 1,
+            title: "<NO_CATEGORY>".to_owned(),
+            last_project_id: 0,
+            entity: "category".to_owned(),
+        }];
+        categories.append(&mut self.data.categories);
+        self.data.categories = categories;
+
+        let removed = self.removed;
+        self.data
+            .transactions
+            .retain(|tx| !removed.contains(&tx.id));
+        // the balances Financisto would keep for the imported transactions
+        let balances = self.data.balances();
+        for account in self.data.accounts.iter_mut() {
+            account.total_amount = balances[&account.id];
+        }
+        self.data
+    }
+
+    fn number(&mut self, id: i32, next: &mut i32) {
+        let left = *next;
+        *next += 1;
+
+        let children = self
+            .parents
+            .iter()
+            .filter(|(_, parent)| *parent == Some(id))
+            .map(|(child, _)| *child)
+            .collect::<Vec<_>>();
+        for child in children {
+            self.number(child, next);
+        }
+
+        let category = self
+            .data
+            .categories
+            .iter_mut()
+            .find(|c| c.id == id)
+            .unwrap();
+        category.left = left;
+        category.right = *next;
+        *next += 1;
+    }
+}
+
+/// Import a Financisto CSV export.
+///
+/// The columns are found by header: `date`, `account` and `amount` are required, `time`,
+/// `currency`, `category`, `parent`, `payee` and `note` are optional. The category is a
+/// `Parent:Child` path, with `parent` put in front when present, and `[Account]` marks a transfer
+/// to that account.
+pub fn import_csv<R: Read>(reader: R, options: &ImportOptions) -> Result<Financisto> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let headers = reader
+        .headers()?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect::<Vec<_>>();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let required = |name: &str| column(name).ok_or_else(|| format!("no '{}' column", name));
+    let (date, account, amount) = (required("date")?, required("account")?, required("amount")?);
+    let (time, currency, category, parent, payee, note) = (
+        column("time"),
+        column("currency"),
+        column("category"),
+        column("parent"),
+        column("payee"),
+        column("note"),
+    );
+
+    let mut builder = Builder::new(options);
+    for (line, record) in reader.records().enumerate() {
+        let record = record?;
+        let field = |index: Option<usize>| index.and_then(|i| record.get(i)).unwrap_or_default();
+        let error = |e| format!("line {}: {}", line + 2, e);
+
+        let day = options.date(field(Some(date))).map_err(error)?;
+        let time = match field(time).trim() {
+            "" => NaiveTime::MIN,
+            time => NaiveTime::parse_from_str(time, "%H:%M:%S")
+                .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+                .map_err(|e| error(e.into()))?,
+        };
+        let account_id = builder.account(field(Some(account)), "OTHER", field(currency));
+        let mut tx = builder.transaction(
+            account_id,
+            options.millis(day, time)?,
+            cents(field(Some(amount))).map_err(error)?,
+        );
+        tx.payee_id = builder.payee(field(payee));
+        tx.note = Some(field(note).to_owned()).filter(|n| !n.is_empty());
+
+        let category = match field(parent).trim() {
+            "" => field(category).to_owned(),
+            parent => format!("{}:{}", parent, field(category)),
+        };
+        match transfer_target(&category) {
+            Some(target) => tx.to_account_id = builder.account(target, "OTHER", field(currency)),
+            None => tx.category_id = builder.category(&category),
+        }
+
+        builder.push(tx, account_id);
+    }
+
+    Ok(builder.finish())
+}
+
+/// Financisto account type of a QIF `!Type:` header.
+fn qif_account_type(kind: &str) -> Option<&'static str> {
+    match kind {
+        "Bank" => Some("BANK"),
+        "Cash" => Some("CASH"),
+        "CCard" => Some("CREDIT_CARD"),
+        "Oth A" | "Oth L" => Some("OTHER"),
+        _ => None,
+    }
+}
+
+/// A QIF transaction, up to its `^`.
+#[derive(Default)]
+struct QifRecord {
+    date: Option<String>,
+    amount: Option<String>,
+    payee: Option<String>,
+    memo: Option<String>,
+    category: Option<String>,
+    status: Option<String>,
+    /// Category, memo and amount of each split line.
+    splits: Vec<(String, Option<String>, Option<String>)>,
+}
+
+/// Import a QIF export.
+///
+/// Accounts come from `!Account` headers, categories from `!Type:Cat` lists and the `L` of each
+/// transaction as `Parent:Child` paths. An `[Account]` category, also in split lines, marks a
+/// transfer, which QIF lists in both accounts but is imported once.
+pub fn import_qif(text: &str, options: &ImportOptions) -> Result<Financisto> {
+    let mut builder = Builder::new(options);
+    let mut account: Option<(String, String)> = None;
+    let mut section = String::new();
+    let mut record = QifRecord::default();
+
+    for (line, text) in text.lines().enumerate() {
+        let text = text.trim_end_matches('\r');
+        if text.trim().is_empty() {
+            continue;
+        }
+        let error = |e| format!("line {}: {}", line + 1, e);
+
+        if let Some(header) = text.strip_prefix('!') {
+            if let Some(kind) = header.strip_prefix("Type:") {
+                section = kind.trim().to_owned();
+            } else if header.trim() == "Account" {
+                section = "Account".to_owned();
+            }
+            // options like !Option:AutoSwitch are ignored
+            continue;
+        }
+
+        let (code, value) = text.split_at(text.chars().next().unwrap().len_utf8());
+        let value = value.trim().to_owned();
+
+        if section == "Account" {
+            match code {
+                "N" => account = Some((value, "OTHER".to_owned())),
+                "T" => {
+                    if let Some((_, r#type)) = account.as_mut() {
+                        *r#type = qif_account_type(&value).unwrap_or("OTHER").to_owned();
+                    }
+                }
+                "^" => {
+                    // the account may already be known as the other side of a transfer
+                    if let Some((title, r#type)) = account.as_ref() {
+                        let id = builder.account(title, r#type, "");
+                        builder.data.accounts[id as usize - 1].r#type = r#type.to_owned();
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if section == "Cat" {
+            if code == "N" {
+                builder.category(&value);
+            }
+            continue;
+        }
+
+        let Some(r#type) = qif_account_type(&section) else {
+            // investments, memorized transactions, classes
+            continue;
+        };
+
+        match code {
+            "D" => record.date = Some(value),
+            "T" | "U" => record.amount = Some(value),
+            "P" => record.payee = Some(value),
+            "M" => record.memo = Some(value),
+            "L" => record.category = Some(value),
+            "C" => record.status = Some(value),
+            "S" => record.splits.push((value, None, None)),
+            "E" => {
+                if let Some(split) = record.splits.last_mut() {
+                    split.1 = Some(value);
+                }
+            }
+            "$" => {
+                if let Some(split) = record.splits.last_mut() {
+                    split.2 = Some(value);
+                }
+            }
+            "^" => {
+                let (title, account_type) = account
+                    .clone()
+                    .unwrap_or(("QIF".to_owned(), r#type.to_owned()));
+                let account_id = builder.account(&title, &account_type, "");
+                let record = std::mem::take(&mut record);
+                push_qif(&mut builder, record, account_id, options).map_err(error)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+fn push_qif(
+    builder: &mut Builder,
+    record: QifRecord,
+    account_id: i32,
+    options: &ImportOptions,
+) -> Result<()> {
+    let date = options.date(record.date.as_deref().ok_or("transaction without date")?)?;
+    let datetime = options.millis(date, NaiveTime::MIN)?;
+    let amount = cents(
+        record
+            .amount
+            .as_deref()
+            .ok_or("transaction without amount")?,
+    )?;
+
+    let mut tx = builder.transaction(account_id, datetime, amount);
+    tx.payee_id = builder.payee(record.payee.as_deref().unwrap_or_default());
+    tx.note = record.memo.filter(|m| !m.is_empty());
+    tx.status = match record.status.as_deref() {
+        Some("*") | Some("c") => Some("CL".to_owned()),
+        Some("X") | Some("R") => Some("RC".to_owned()),
+        _ => None,
+    };
+
+    let category = |builder: &mut Builder, tx: &mut FinancistoTransaction, category: &str| {
+        match transfer_target(category) {
+            Some(target) => tx.to_account_id = builder.account(target, "OTHER", ""),
+            None => tx.category_id = builder.category(category),
+        }
+    };
+
+    if record.splits.is_empty() {
+        category(
+            builder,
+            &mut tx,
+            record.category.as_deref().unwrap_or_default(),
+        );
+        builder.push(tx, account_id);
+        return Ok(());
+    }
+
+    tx.category_id = SPLIT_CATEGORY_ID;
+    let parent_id = tx.id;
+    builder.push(tx, account_id);
+
+    for (split, memo, amount) in record.splits {
+        let amount = cents(amount.as_deref().ok_or("split line without amount")?)?;
+        let mut line = builder.transaction(account_id, datetime, amount);
+        line.parent_id = parent_id;
+        line.note = memo.filter(|m| !m.is_empty());
+        category(builder, &mut line, &split);
+        builder.push(line, account_id);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use crate::{
+    Financisto, FinancistoAccount, FinancistoCategory, FinancistoCurrency, FinancistoPayee,
+    FinancistoTransaction,
+};
+
+/// Lookups by ID and split lines by parent over a [`Financisto`], built in one pass so the
+/// migration stays linear in the number of transactions.
+///
+/// The index borrows the data, build a new one after changing it.
+#[derive(Debug, Clone)]
+pub struct FinancistoIndex<'a> {
+    currencies: HashMap<i32, &'a FinancistoCurrency>,
+    accounts: HashMap<i32, &'a FinancistoAccount>,
+    categories: HashMap<i32, &'a FinancistoCategory>,
+    payees: HashMap<i32, &'a FinancistoPayee>,
+    transactions: HashMap<i32, &'a FinancistoTransaction>,
+    /// Split lines by the ID of their parent, in backup order.
+    split_lines: HashMap<i32, Vec<&'a FinancistoTransaction>>,
+}
+
+impl<'a> FinancistoIndex<'a> {
+    pub fn new(data: &'a Financisto) -> Self {
+        let mut split_lines: HashMap<i32, Vec<_>> = HashMap::new();
+        for tx in data.transactions.iter().filter(|t| t.parent_id != 0) {
+            split_lines.entry(tx.parent_id).or_default().push(tx);
+        }
+
+        Self {
+            currencies: data.currencies.iter().map(|c| (c.id, c)).collect(),
+            accounts: data.accounts.iter().map(|a| (a.id, a)).collect(),
+            categories: data.categories.iter().map(|c| (c.id, c)).collect(),
+            payees: data.payees.iter().map(|p| (p.id, p)).collect(),
+            transactions: data.transactions.iter().map(|t| (t.id, t)).collect(),
+            split_lines,
+        }
+    }
+
+    pub fn currency(&self, id: i32) -> Option<&'a FinancistoCurrency> {
+        self.currencies.get(&id).copied()
+    }
+
+    pub fn account(&self, id: i32) -> Option<&'a FinancistoAccount> {
+        self.accounts.get(&id).copied()
+    }
+
+    pub fn category(&self, id: i32) -> Option<&'a FinancistoCategory> {
+        self.categories.get(&id).copied()
+    }
+
+    pub fn payee(&self, id: i32) -> Option<&'a FinancistoPayee> {
+        self.payees.get(&id).copied()
+    }
+
+    pub fn transaction(&self, id: i32) -> Option<&'a FinancistoTransaction> {
+        self.transactions.get(&id).copied()
+    }
+
+    /// Lines of a split transaction, empty if it is not split.
+    pub fn split_lines(&self, parent_id: i32) -> &[&'a FinancistoTransaction] {
+        self.split_lines
+            .get(&parent_id)
+            .map(|lines| lines.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl Financisto {
+    /// Build the lookups by ID, see [`FinancistoIndex`].
+    pub fn index(&self) -> FinancistoIndex<'_> {
+        FinancistoIndex::new(self)
+    }
+}
@@ -0,0 +1,28 @@
+use std::fs;
+use std::path::Path;
+
+use crate::{Financisto, Result};
+
+impl Financisto {
+    /// The data as pretty JSON, one array per kind of entity and the raw entities as field maps.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Data dumped by [`Financisto::to_json`], possibly edited: a missing kind of entity is empty
+    /// and a missing optional field is NULL.
+    pub fn from_json(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+}
+
+/// Write the data to a JSON file, see [`Financisto::to_json`].
+pub fn write_json<P: AsRef<Path>>(data: &Financisto, path: P) -> Result<()> {
+    fs::write(path, data.to_json()?)?;
+    Ok(())
+}
+
+/// Read a JSON file written by [`write_json`], see [`Financisto::from_json`].
+pub fn read_json<P: AsRef<Path>>(path: P) -> Result<Financisto> {
+    Financisto::from_json(&fs::read_to_string(path)?)
+}
@@ -1,12 +1,56 @@
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
-use chrono::TimeZone;
 use log::*;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+mod backup;
+mod bluecoins;
+mod category;
+mod check;
+mod database;
+mod entity;
+mod filter;
+mod import;
+mod index;
+#[cfg(feature = "serialize")]
+mod json;
+mod names;
+mod report;
+mod rules;
+mod schedule;
+mod schema;
+mod sql;
+mod status;
+mod timezone;
+mod verify;
+
+pub use backup::*;
+pub use bluecoins::*;
+pub use category::*;
+pub use check::*;
+pub use database::*;
+pub use entity::*;
+pub use filter::*;
+pub use import::*;
+pub use index::*;
+#[cfg(feature = "serialize")]
+pub use json::*;
+pub use names::*;
+pub use report::*;
+pub use rules::*;
+pub use schedule::*;
+pub use schema::*;
+pub use sql::*;
+pub use status::*;
+pub use timezone::*;
+pub use verify::*;
 
 pub type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct FinancistoCurrency {
     pub id: i32,
     pub decimal_separator: Option<String>,
@@ -22,32 +66,41 @@ pub struct FinancistoCurrency {
     pub group_separator: Option<String>,
 }
 
-impl From<&HashMap<String, String>> for FinancistoCurrency {
-    fn from(map: &HashMap<String, String>) -> Self {
-        Self {
-            id: map.get("_id").unwrap().parse().unwrap(),
-            decimal_separator: map.get("decimal_separator").map(|v| v.to_owned()),
-            updated_on: map.get("updated_on").unwrap().parse().unwrap(),
-            title: map.get("title").unwrap().to_owned(),
-            entity: map.get("entity").unwrap().to_owned(),
-            decimals: map.get("decimals").unwrap().parse().unwrap(),
-            symbol: map.get("symbol").unwrap().to_owned(),
-            is_active: map.get("is_active").unwrap().parse().unwrap(),
-            name: map.get("name").unwrap().to_owned(),
-            is_default: map.get("is_default").unwrap().parse().unwrap(),
-            symbol_format: map.get("symbol_format").unwrap().to_owned(),
-            group_separator: map.get("group_separator").map(|v| v.to_owned()),
-        }
+impl TryFrom<&HashMap<String, String>> for FinancistoCurrency {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(map: &HashMap<String, String>) -> Result<Self> {
+        Self::from_fields(map)
     }
 }
 
-#[derive(Debug)]
+impl FromFields for FinancistoCurrency {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self> {
+        Ok(Self {
+            id: fields.required("_id")?,
+            decimal_separator: fields.optional("decimal_separator"),
+            updated_on: fields.required("updated_on")?,
+            title: fields.text("title")?,
+            entity: fields.text("entity")?,
+            decimals: fields.required("decimals")?,
+            symbol: fields.text("symbol")?,
+            is_active: fields.required("is_active")?,
+            name: fields.text("name")?,
+            is_default: fields.required("is_default")?,
+            symbol_format: fields.text("symbol_format")?,
+            group_separator: fields.optional("group_separator"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct FinancistoAccount {
     pub id: i32,
     pub note: Option<String>,
     pub total_limit: i32,
     pub entity: String,
-    pub total_amount: i32,
+    pub total_amount: i64,
     pub last_transaction_date: i64,
     pub payment_day: i32,
     pub sort_order: i32,
@@ -65,34 +118,43 @@ pub struct FinancistoAccount {
     pub is_include_into_totals: i32,
 }
 
-impl From<&HashMap<String, String>> for FinancistoAccount {
-    fn from(map: &HashMap<String, String>) -> Self {
-        Self {
-            id: map.get("_id").unwrap().parse().unwrap(),
-            note: map.get("note").map(|v| v.to_owned()),
-            total_limit: map.get("total_limit").unwrap().parse().unwrap(),
-            entity: map.get("entity").unwrap().to_owned(),
-            total_amount: map.get("total_amount").unwrap().parse().unwrap(),
-            last_transaction_date: map.get("last_transaction_date").unwrap().parse().unwrap(),
-            payment_day: map.get("payment_day").unwrap().parse().unwrap(),
-            sort_order: map.get("sort_order").unwrap().parse().unwrap(),
-            r#type: map.get("type").unwrap().to_owned(),
-            updated_on: map.get("updated_on").unwrap().parse().unwrap(),
-            is_active: map.get("is_active").unwrap().parse().unwrap(),
-            issuer: map.get("issuer").map(|v| v.to_owned()),
-            title: map.get("title").unwrap().to_owned(),
-            creation_date: map.get("creation_date").unwrap().parse().unwrap(),
-            last_account_id: map.get("last_account_id").unwrap().parse().unwrap(),
-            last_category_id: map.get("last_category_id").unwrap().parse().unwrap(),
-            currency_id: map.get("currency_id").unwrap().parse().unwrap(),
-            closing_day: map.get("closing_day").unwrap().parse().unwrap(),
-            card_issuer: map.get("card_issuer").map(|v| v.to_owned()),
-            is_include_into_totals: map.get("is_include_into_totals").unwrap().parse().unwrap(),
-        }
+impl TryFrom<&HashMap<String, String>> for FinancistoAccount {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(map: &HashMap<String, String>) -> Result<Self> {
+        Self::from_fields(map)
+    }
+}
+
+impl FromFields for FinancistoAccount {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self> {
+        Ok(Self {
+            id: fields.required("_id")?,
+            note: fields.optional("note"),
+            total_limit: fields.required("total_limit")?,
+            entity: fields.text("entity")?,
+            total_amount: fields.required("total_amount")?,
+            last_transaction_date: fields.required("last_transaction_date")?,
+            payment_day: fields.required("payment_day")?,
+            sort_order: fields.required("sort_order")?,
+            r#type: fields.text("type")?,
+            updated_on: fields.required("updated_on")?,
+            is_active: fields.required("is_active")?,
+            issuer: fields.optional("issuer"),
+            title: fields.text("title")?,
+            creation_date: fields.required("creation_date")?,
+            last_account_id: fields.required("last_account_id")?,
+            last_category_id: fields.required("last_category_id")?,
+            currency_id: fields.required("currency_id")?,
+            closing_day: fields.required("closing_day")?,
+            card_issuer: fields.optional("card_issuer"),
+            is_include_into_totals: fields.required("is_include_into_totals")?,
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct FinancistoCategory {
     pub r#type: i32,
     pub id: i32,
@@ -106,24 +168,33 @@ pub struct FinancistoCategory {
     pub entity: String,
 }
 
-impl From<&HashMap<String, String>> for FinancistoCategory {
-    fn from(map: &HashMap<String, String>) -> Self {
-        Self {
-            r#type: map.get("type").unwrap().parse().unwrap(),
-            id: map.get("_id").unwrap().parse().unwrap(),
-            updated_on: map.get("updated_on").unwrap().parse().unwrap(),
-            right: map.get("right").unwrap().parse().unwrap(),
-            left: map.get("left").unwrap().parse().unwrap(),
-            last_location_id: map.get("last_location_id").unwrap().parse().unwrap(),
-            is_active: map.get("is_active").unwrap().parse().unwrap(),
-            title: map.get("title").unwrap().to_owned(),
-            last_project_id: map.get("last_project_id").unwrap().parse().unwrap(),
-            entity: map.get("entity").unwrap().to_owned(),
-        }
+impl TryFrom<&HashMap<String, String>> for FinancistoCategory {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(map: &HashMap<String, String>) -> Result<Self> {
+        Self::from_fields(map)
     }
 }
 
-#[derive(Debug)]
+impl FromFields for FinancistoCategory {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self> {
+        Ok(Self {
+            r#type: fields.required("type")?,
+            id: fields.required("_id")?,
+            updated_on: fields.required("updated_on")?,
+            right: fields.required("right")?,
+            left: fields.required("left")?,
+            last_location_id: fields.required("last_location_id")?,
+            is_active: fields.required("is_active")?,
+            title: fields.text("title")?,
+            last_project_id: fields.required("last_project_id")?,
+            entity: fields.text("entity")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct FinancistoTransaction {
     pub longitude: f32,
     pub from_account_id: i32,
@@ -135,6 +206,7 @@ pub struct FinancistoTransaction {
     pub project_id: i32,
     pub provider: Option<String>,
     pub template_name: Option<String>,
+    pub recurrence: Option<String>,
     pub latitude: f32,
     pub accuracy: f32,
     pub status: Option<String>,
@@ -152,157 +224,446 @@ pub struct FinancistoTransaction {
     pub id: i32,
 }
 
-impl From<&HashMap<String, String>> for FinancistoTransaction {
-    fn from(map: &HashMap<String, String>) -> Self {
-        Self {
-            longitude: map.get("longitude").unwrap().parse().unwrap(),
-            from_account_id: map.get("from_account_id").unwrap().parse().unwrap(),
-            location_id: map.get("location_id").unwrap().parse().unwrap(),
-            note: map.get("note").map(|v| v.to_owned()),
-            to_account_id: map.get("to_account_id").unwrap().parse().unwrap(),
-            entity: map.get("entity").unwrap().to_owned(),
-            datetime: map.get("datetime").unwrap().parse().unwrap(),
-            project_id: map.get("project_id").unwrap().parse().unwrap(),
-            provider: map.get("provider").map(|v| v.to_owned()),
-            template_name: map.get("template_name").map(|v| v.to_owned()),
-            latitude: map.get("latitude").unwrap().parse().unwrap(),
-            accuracy: map.get("accuracy").unwrap().parse().unwrap(),
-            status: map.get("v").map(|v| v.to_owned()),
-            is_ccard_payment: map.get("is_ccard_payment").unwrap().parse().unwrap(),
-            payee_id: map.get("payee_id").unwrap().parse().unwrap(),
-            category_id: map.get("category_id").unwrap().parse().unwrap(),
-            parent_id: map.get("parent_id").unwrap().parse().unwrap(),
-            to_amount: map.get("to_amount").unwrap().parse().unwrap(),
-            from_amount: map.get("from_amount").unwrap().parse().unwrap(),
-            original_currency_id: map.get("original_currency_id").unwrap().parse().unwrap(),
-            original_from_amount: map.get("original_from_amount").unwrap().parse().unwrap(),
-            last_recurrence: map.get("last_recurrence").unwrap().parse().unwrap(),
-            is_template: map.get("is_template").unwrap().parse().unwrap(),
-            updated_on: map.get("updated_on").unwrap().parse().unwrap(),
-            id: map.get("_id").unwrap().parse().unwrap(),
-        }
+impl TryFrom<&HashMap<String, String>> for FinancistoTransaction {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(map: &HashMap<String, String>) -> Result<Self> {
+        Self::from_fields(map)
     }
 }
 
+impl FromFields for FinancistoTransaction {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self> {
+        Ok(Self {
+            longitude: fields.required("longitude")?,
+            from_account_id: fields.required("from_account_id")?,
+            location_id: fields.required("location_id")?,
+            note: fields.optional("note"),
+            to_account_id: fields.required("to_account_id")?,
+            entity: fields.text("entity")?,
+            datetime: fields.required("datetime")?,
+            project_id: fields.required("project_id")?,
+            provider: fields.optional("provider"),
+            template_name: fields.optional("template_name"),
+            recurrence: fields.optional("recurrence"),
+            latitude: fields.required("latitude")?,
+            accuracy: fields.required("accuracy")?,
+            status: fields.optional("status"),
+            is_ccard_payment: fields.required("is_ccard_payment")?,
+            payee_id: fields.required("payee_id")?,
+            category_id: fields.required("category_id")?,
+            parent_id: fields.required("parent_id")?,
+            to_amount: fields.required("to_amount")?,
+            from_amount: fields.required("from_amount")?,
+            original_currency_id: fields.required("original_currency_id")?,
+            original_from_amount: fields.required("original_from_amount")?,
+            last_recurrence: fields.required("last_recurrence")?,
+            is_template: fields.required("is_template")?,
+            updated_on: fields.required("updated_on")?,
+            id: fields.required("_id")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FinancistoPayee {
+    pub id: i32,
+    pub title: String,
+    pub last_category_id: i32,
+    pub is_active: i32,
+    pub updated_on: i64,
+    pub entity: String,
+}
+
+impl TryFrom<&HashMap<String, String>> for FinancistoPayee {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(map: &HashMap<String, String>) -> Result<Self> {
+        Self::from_fields(map)
+    }
+}
+
+impl FromFields for FinancistoPayee {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self> {
+        Ok(Self {
+            id: fields.required("_id")?,
+            title: fields.text("title")?,
+            last_category_id: fields.required("last_category_id")?,
+            is_active: fields.required("is_active")?,
+            updated_on: fields.required("updated_on")?,
+            entity: fields.text("entity")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FinancistoProject {
+    pub id: i32,
+    pub title: String,
+    pub is_active: i32,
+    pub updated_on: i64,
+    pub entity: String,
+}
+
+impl TryFrom<&HashMap<String, String>> for FinancistoProject {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(map: &HashMap<String, String>) -> Result<Self> {
+        Self::from_fields(map)
+    }
+}
+
+impl FromFields for FinancistoProject {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self> {
+        Ok(Self {
+            id: fields.required("_id")?,
+            title: fields.text("title")?,
+            is_active: fields.required("is_active")?,
+            updated_on: fields.required("updated_on")?,
+            entity: fields.text("entity")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FinancistoLocation {
+    pub id: i32,
+    pub name: String,
+    pub datetime: i64,
+    pub provider: Option<String>,
+    pub accuracy: f32,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub is_payee: i32,
+    pub resolved_address: Option<String>,
+    pub count: i32,
+    pub updated_on: i64,
+    pub entity: String,
+}
+
+impl TryFrom<&HashMap<String, String>> for FinancistoLocation {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(map: &HashMap<String, String>) -> Result<Self> {
+        Self::from_fields(map)
+    }
+}
+
+impl FromFields for FinancistoLocation {
+    fn from_fields<F: Fields>(fields: &F) -> Result<Self> {
+        Ok(Self {
+            id: fields.required("_id")?,
+            name: fields.text("name")?,
+            datetime: fields.required("datetime")?,
+            provider: fields.optional("provider"),
+            accuracy: fields.required("accuracy")?,
+            latitude: fields.required("latitude")?,
+            longitude: fields.required("longitude")?,
+            is_payee: fields.required("is_payee")?,
+            resolved_address: fields.optional("resolved_address"),
+            count: fields.required("count")?,
+            updated_on: fields.required("updated_on")?,
+            entity: fields.text("entity")?,
+        })
+    }
+}
+
+/// Everything read from a backup or database, see [`Financisto::to_json`] with the `serialize`
+/// feature to edit it before the migration.
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
 pub struct Financisto {
     pub currencies: Vec<FinancistoCurrency>,
     pub accounts: Vec<FinancistoAccount>,
     pub categories: Vec<FinancistoCategory>,
+    pub payees: Vec<FinancistoPayee>,
+    pub projects: Vec<FinancistoProject>,
+    pub locations: Vec<FinancistoLocation>,
     pub transactions: Vec<FinancistoTransaction>,
+    /// The entities of the kinds not modeled above as they were read, and the fields seen in
+    /// every kind.
+    pub raw: RawEntities,
 }
 
 impl Financisto {
     pub fn new() -> Financisto {
-        Self {
-            currencies: Vec::new(),
-            accounts: Vec::new(),
-            categories: Vec::new(),
-            transactions: Vec::new(),
-        }
+        Self::default()
     }
 }
 
-pub fn convert_maps(lines: &Vec<String>) -> Result<Financisto> {
+/// Where the note of a split parent ends up, the parent itself is not migrated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitNote {
+    /// Only used as the item name of the split lines.
+    ItemOnly,
+    /// Also put in front of the notes of every split line.
+    #[default]
+    Notes,
+}
+
+/// Settings that shape how Financisto data is mapped onto Bluecoins.
+#[derive(Debug, Default)]
+pub struct MigrateOptions {
+    pub category_flatten: CategoryFlatten,
+    pub rules: Rules,
+    pub item_names: ItemNames,
+    pub status: StatusMap,
+    pub split_note: SplitNote,
+    pub integrity: Integrity,
+    pub filter: Filter,
+    /// Timezone of every date written to Bluecoins.
+    pub timezone: Timezone,
+}
+
+/// Parse the lines of a backup, after its 4 header lines.
+///
+/// Financisto writes each value as it is after the first `:` of its line, without escaping, so
+/// values may contain colons and `$$`. Notes with line breaks span several lines: a line that
+/// does not start with a column name of the entity continues the value before it, and `$$` only
+/// ends an entity when the next entity, `#END` or the end of the file follows it.
+pub fn convert_maps(lines: &[String]) -> Result<Financisto> {
     let mut data = Financisto::new();
     let mut index = 4;
 
     while index < lines.len() {
-        let line = lines.get(index).unwrap();
+        let line = lines[index].trim_end_matches('\r');
 
-        if "#START" == line || "#END" == line {
-            index = index + 1;
-        } else {
-            if line.starts_with("$ENTITY:") {
-                let map = convert_entity(lines, &mut index);
-
-                if let Some(entity) = map.get("entity") {
-                    match entity.as_ref() {
-                        "currency" => {
-                            trace!("{} = {:?}", entity, &map);
-                            let item: FinancistoCurrency = (&map).into();
-                            debug!("{:?}", &item);
-                            data.currencies.push(item);
-                        }
-                        "account" => {
-                            trace!("{} = {:?}", entity, &map);
-                            let item: FinancistoAccount = (&map).into();
-                            debug!("{:?}", &item);
-                            data.accounts.push(item);
-                        }
-                        "category" => {
-                            trace!("{} = {:?}", entity, &map);
-                            let item: FinancistoCategory = (&map).into();
-                            debug!("{:?}", &item);
-                            data.categories.push(item);
-                        }
-                        "transactions" => {
-                            trace!("{} = {:?}", entity, &map);
-                            let item: FinancistoTransaction = (&map).into();
-                            debug!("{:?}", &item);
-                            data.transactions.push(item);
-                        }
-                        _ => {}
-                    }
+        if !line.starts_with("$ENTITY:") {
+            if !line.is_empty() && "#START" != line && "#END" != line {
+                warn!("line {} is outside of any entity: {}", index + 1, line);
+            }
+            index += 1;
+            continue;
+        }
+
+        let map = convert_entity(lines, &mut index);
+
+        if let Some(entity) = map.get("entity") {
+            match entity.as_ref() {
+                "currency" => {
+                    trace!("{} = {:?}", entity, &map);
+                    let item = FinancistoCurrency::from_fields(&map)?;
+                    debug!("{:?}", &item);
+                    data.currencies.push(item);
+                }
+                "account" => {
+                    trace!("{} = {:?}", entity, &map);
+                    let item = FinancistoAccount::from_fields(&map)?;
+                    debug!("{:?}", &item);
+                    data.accounts.push(item);
+                }
+                "category" => {
+                    trace!("{} = {:?}", entity, &map);
+                    let item = FinancistoCategory::from_fields(&map)?;
+                    debug!("{:?}", &item);
+                    data.categories.push(item);
+                }
+                "payee" => {
+                    trace!("{} = {:?}", entity, &map);
+                    let item = FinancistoPayee::from_fields(&map)?;
+                    debug!("{:?}", &item);
+                    data.payees.push(item);
+                }
+                "project" => {
+                    trace!("{} = {:?}", entity, &map);
+                    let item = FinancistoProject::from_fields(&map)?;
+                    debug!("{:?}", &item);
+                    data.projects.push(item);
+                }
+                "locations" => {
+                    trace!("{} = {:?}", entity, &map);
+                    let item = FinancistoLocation::from_fields(&map)?;
+                    debug!("{:?}", &item);
+                    data.locations.push(item);
+                }
+                "transactions" => {
+                    trace!("{} = {:?}", entity, &map);
+                    let item = FinancistoTransaction::from_fields(&map)?;
+                    debug!("{:?}", &item);
+                    data.transactions.push(item);
+                }
+                _ => {
+                    data.raw.push(map.into());
+                    continue;
                 }
             }
+            data.raw.read(entity, map.keys().map(|k| k.as_str()));
+        } else {
+            data.raw.push(map.into());
         }
     }
 
     Ok(data)
 }
 
-fn convert_entity(lines: &Vec<String>, index: &mut usize) -> HashMap<String, String> {
-    let mut map: HashMap<String, String> = HashMap::new();
+/// Whether the line starts a `name:value` field, rather than continuing a multi-line value.
+///
+/// Any lower case name starts a field, also the columns a newer Financisto adds, so a note line
+/// like `todo: call bank` starts a field of its own.
+pub(crate) fn is_field(line: &str) -> bool {
+    match line.split_once(':') {
+        Some(("$ENTITY", _)) => true,
+        Some((name, _)) => {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        }
+        None => false,
+    }
+}
 
-    loop {
-        let line = lines.get(*index).unwrap();
+/// Whether the line at `index` is the `$$` ending an entity.
+fn is_entity_end(lines: &[String], index: usize) -> bool {
+    let line = |index: usize| lines.get(index).map(|l| l.trim_end_matches('\r'));
 
-        *index = *index + 1;
+    line(index) == Some("$$")
+        && match line(index + 1) {
+            None | Some("#END") => true,
+            Some(next) => next.starts_with("$ENTITY:"),
+        }
+}
+
+fn convert_entity(lines: &[String], index: &mut usize) -> HashMap<String, String> {
+    let mut map: HashMap<String, String> = HashMap::new();
+    let mut last: Option<String> = None;
 
-        if "$$" == line {
+    while *index < lines.len() {
+        let end = is_entity_end(lines, *index);
+        let line = lines[*index].trim_end_matches('\r');
+        *index += 1;
+
+        if end {
             break;
-        } else {
-            if let Some(split_position) = line.find(':') {
-                let name = line[..split_position].to_owned();
-                let value = line[split_position + 1..].to_owned();
-                match name.as_str() {
-                    "$ENTITY" => map.insert("entity".to_owned(), value),
-                    _ => map.insert(name, value),
+        }
+        if line.starts_with("$ENTITY:") && map.contains_key("entity") {
+            // the `$$` of this entity is missing
+            *index -= 1;
+            break;
+        }
+
+        match line.split_once(':').filter(|_| is_field(line)) {
+            Some((name, value)) => {
+                let name = match name {
+                    "$ENTITY" => "entity",
+                    _ => name,
                 };
+                map.insert(name.to_owned(), value.to_owned());
+                last = Some(name.to_owned());
             }
+            None => match last.as_ref().and_then(|name| map.get_mut(name)) {
+                Some(value) => {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+                None => warn!("line {} is not a field: {}", *index, line),
+            },
         }
     }
 
     map
 }
 
-pub fn print_entity(list: &Vec<HashMap<String, String>>) {
-    let entities = list
+/// Log the kinds of entities read and how many there are of each.
+pub fn print_entity(entities: &RawEntities) {
+    for entity in entities.types() {
+        info!("entity = {} ({})", entity, entities.count(entity));
+    }
+}
+
+/// Take the first `count` consecutive transaction IDs from `first` on that are not `used` yet,
+/// as transactions at the same millisecond would otherwise share their IDs.
+fn take_ids(used: &mut HashSet<i64>, mut first: i64, count: i64) -> i64 {
+    while let Some(id) = (first..first + count).rfind(|id| used.contains(id)) {
+        first = id + 1;
+    }
+    used.extend(first..first + count);
+    first
+}
+
+/// Largest amount in cents that converts to Bluecoins' 6 digits without overflowing.
+const MAX_AMOUNT: i64 = i64::MAX / 1000000;
+/// Largest account or category ID, leaving room for the Bluecoins offsets and for the categories
+/// created after the last one.
+const MAX_ID: i32 = i32::MAX / 2;
+
+/// Refuse the amounts and IDs no book has, which would overflow on their way to Bluecoins.
+fn check_ranges(data: &Financisto) -> Result<()> {
+    let ids = data
+        .accounts
         .iter()
-        .map(|m| m.get("entity").unwrap())
-        .fold(&mut HashSet::new(), |set, x| {
-            set.insert(x);
-            set
-        })
-        .clone();
+        .map(|a| ("account", a.id, a.id))
+        .chain(data.categories.iter().map(|c| ("category", c.id, c.id)))
+        .chain(data.transactions.iter().flat_map(|tx| {
+            [
+                ("transaction", tx.id, tx.from_account_id),
+                ("transaction", tx.id, tx.to_account_id),
+            ]
+        }));
+    for (entity, id, value) in ids {
+        if value > MAX_ID {
+            return Err(format!("{} {} has an ID out of range: {}", entity, id, value).into());
+        }
+    }
 
-    for entity in entities.iter() {
-        info!("entity = {}", entity);
+    for tx in data.transactions.iter() {
+        for amount in [tx.from_amount, tx.to_amount, tx.original_from_amount] {
+            if !(-MAX_AMOUNT..=MAX_AMOUNT).contains(&amount) {
+                return Err(format!(
+                    "transaction {} has an amount out of range: {}",
+                    tx.id, amount
+                )
+                .into());
+            }
+        }
     }
+
+    Ok(())
 }
 
-fn escape_quote(text: &str) -> String {
+/// Balance of each account before the migrated date range, by Financisto account ID, summed in one
+/// pass over the transactions.
+pub(crate) fn opening_balances(data: &Financisto, filter: &FilterSet) -> Result<HashMap<i32, i64>> {
+    let mut balances: HashMap<i32, i64> = HashMap::new();
+    let before = data
+        .transactions
+        .iter()
+        .filter(|tx| tx.parent_id == 0 && tx.is_template == 0 && filter.before(tx.datetime));
+    for tx in before {
+        let sides = [
+            (tx.from_account_id, tx.from_amount),
+            (tx.to_account_id, tx.to_amount),
+        ];
+        for (account_id, amount) in sides.into_iter().filter(|(id, _)| *id != 0) {
+            let balance = balances.entry(account_id).or_default();
+            *balance = balance.checked_add(amount).ok_or_else(|| {
+                format!("opening balance of account {} is out of range", account_id)
+            })?;
+        }
+    }
+
+    Ok(balances)
+}
+
+pub(crate) fn escape_quote(text: &str) -> String {
     text.replace("'", "''")
 }
 
-pub fn migrate_accounts(
-    accounts: &Vec<FinancistoAccount>,
-    currencies: &Vec<FinancistoCurrency>,
-) -> Result<Vec<String>> {
-    let mut item_id = 5;
+pub fn migrate_accounts(data: &Financisto, options: &MigrateOptions) -> Result<Vec<String>> {
+    check_ranges(data)?;
+    let index = data.index();
+    let filter = options.filter.resolve(data, options.timezone)?;
+    let opening = opening_balances(data, &filter)?;
     let mut statements = Vec::new();
 
-    for account in accounts.iter() {
+    let accounts = first_of_ids("account", &data.accounts, |a| a.id, options.integrity)?
+        .into_iter()
+        .filter(|a| filter.account(a.id));
+    for (item_id, account) in (5..).zip(accounts) {
         debug!("{}: {:?}\n", account.entity, account);
 
         // map Financisto account type to bluecoins account type (ACCOUNTTYPETABLE)
@@ -317,23 +678,37 @@ pub fn migrate_accounts(
             _ => "",
         };
 
-        let currency = currencies
-            .iter()
-            .find(|c| c.id == account.currency_id)
-            .unwrap()
-            .name
-            .to_string();
-        let create_time = chrono::Local
-            .timestamp_millis_opt(account.creation_date)
-            .unwrap();
+        let currency = if let Some(currency) = index.currency(account.currency_id) {
+            currency.name.to_string()
+        } else {
+            warn!(
+                "account {} refers to unknown currency {}, migrated as TWD",
+                account.id, account.currency_id
+            );
+            "TWD".to_owned()
+        };
+
+        // billing cycle of credit cards
+        let (credit_limit, cut_off_day, due_day) = if account.r#type == "CREDIT_CARD" {
+            (
+                account.total_limit as i64 * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
+                account.closing_day,
+                account.payment_day,
+            )
+        } else {
+            (0, 0, 0)
+        };
 
         // account
         statements.push(format!(
-            "INSERT INTO \"ACCOUNTSTABLE\" (\"accountsTableID\", \"accountName\", \"accountTypeID\", \"accountHidden\", \"accountCurrency\", \"accountConversionRateNew\", \"currencyChanged\", \"creditLimit\", \"cutOffDa\", \"creditCardDueDate\", \"cashBasedAccounts\", \"accountSelectorVisibility\", \"accountsExtraColumnInt1\", \"accountsExtraColumnInt2\", \"accountsExtraColumnString1\", \"accountsExtraColumnString2\") VALUES ('{}', '{}', '{}', '0', '{}', '1.0', NULL, '0', '0', '0', '0', '0', NULL, NULL, NULL, NULL);",
+            "INSERT INTO \"ACCOUNTSTABLE\" (\"accountsTableID\", \"accountName\", \"accountTypeID\", \"accountHidden\", \"accountCurrency\", \"accountConversionRateNew\", \"currencyChanged\", \"creditLimit\", \"cutOffDa\", \"creditCardDueDate\", \"cashBasedAccounts\", \"accountSelectorVisibility\", \"accountsExtraColumnInt1\", \"accountsExtraColumnInt2\", \"accountsExtraColumnString1\", \"accountsExtraColumnString2\") VALUES ('{}', '{}', '{}', '0', '{}', '1.0', NULL, '{}', '{}', '{}', '0', '0', NULL, NULL, NULL, NULL);",
             account.id + 5,
             escape_quote(&account.title),
             account_type,
             currency,
+            credit_limit,
+            cut_off_day,
+            due_day,
         ));
 
         // item
@@ -343,47 +718,95 @@ pub fn migrate_accounts(
             escape_quote(&account.title),
         ));
 
-        // init transaction
-        statements.push(format!(
-            "INSERT INTO \"TRANSACTIONSTABLE\" (\"transactionsTableID\", \"itemID\", \"amount\", \"transactionCurrency\", \"conversionRateNew\", \"date\", \"transactionTypeID\", \"categoryID\", \"accountID\", \"notes\", \"status\", \"accountReference\", \"accountPairID\", \"uidPairID\", \"deletedTransaction\", \"newSplitTransactionID\", \"transferGroupID\", \"reminderTransaction\", \"reminderGroupID\", \"reminderFrequency\", \"reminderRepeatEvery\", \"reminderEndingType\", \"reminderStartDate\", \"reminderEndDate\", \"reminderAfterNoOfOccurences\", \"reminderAutomaticLogTransaction\", \"reminderRepeatByDayOfMonth\", \"reminderExcludeWeekend\", \"reminderWeekDayMoveSetting\", \"reminderUnbilled\", \"creditCardInstallment\", \"reminderVersion\", \"dataExtraColumnString1\") VALUES ('{transactionsTableID}', '{itemID}', '{amount}', '{transactionCurrency}', '{conversionRateNew}', '{date}', '{transactionTypeID}', '{categoryID}', '{accountID}', '{notes}', '{status}', '{accountReference}', '{accountPairID}', '{uidPairID}', '{deletedTransaction}', '{newSplitTransactionID}', '{transferGroupID}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);",
-            transactionsTableID = account.id + 5,
-            itemID = item_id,
-            amount = 0,
-            transactionCurrency = currency,
-            conversionRateNew = 1.0,
-            date = create_time.format("%Y-%m-%d %H:%M:%S"),
-            transactionTypeID  = 2,
-            categoryID = 2,
-            accountID = account.id + 5,
-            notes = "",
-            status = 2,
-            accountReference = 3,
-            accountPairID = account.id + 5,
-            uidPairID = account.creation_date,
-            deletedTransaction = 6,
-            newSplitTransactionID = 0,
-            transferGroupID = 0,
-        ));
+        // balance before the migrated date range
+        let opening_balance = opening.get(&account.id).copied().unwrap_or_default();
+        if !(-MAX_AMOUNT..=MAX_AMOUNT).contains(&opening_balance) {
+            return Err(
+                format!("opening balance of account {} is out of range", account.id).into(),
+            );
+        }
 
-        item_id = item_id + 1;
+        // init transaction
+        statements.push(
+            BluecoinsTransaction {
+                transactions_table_id: (account.id + 5) as i64,
+                item_id,
+                amount: opening_balance * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
+                transaction_currency: currency.to_owned(),
+                conversion_rate_new: 1.0,
+                date: options.timezone.format(account.creation_date)?,
+                transaction_type_id: 2,
+                category_id: 2,
+                account_id: account.id + 5,
+                notes: String::new(),
+                status: 2,
+                account_reference: 3,
+                account_pair_id: account.id + 5,
+                uid_pair_id: account.creation_date,
+                deleted_transaction: 6,
+                new_split_transaction_id: 0,
+                transfer_group_id: 0,
+                reminder: None,
+            }
+            .to_sql(),
+        );
     }
 
     Ok(statements)
 }
 
-pub fn migrate_categories(categories: &Vec<FinancistoCategory>) -> Result<Vec<String>> {
+pub fn migrate_categories(data: &Financisto, options: &MigrateOptions) -> Result<Vec<String>> {
+    check_ranges(data)?;
     let mut statements = Vec::new();
+    let categories = first_of_ids("category", &data.categories, |c| c.id, options.integrity)?
+        .into_iter()
+        .filter(|c| !is_system_category(c.id))
+        .cloned()
+        .collect::<Vec<_>>();
+    let tree = CategoryTree::new(&categories);
+    let mut flat = tree.flatten(options.category_flatten);
+
+    // partial migrations only bring the categories their transactions use
+    let mut uncategorized = true;
+    if !options.filter.is_empty() {
+        let filter = options.filter.resolve(data, options.timezone)?;
+        let rules = options.rules.resolve(data)?;
+        // split parents with lines are no rows of their own
+        let parents = data
+            .transactions
+            .iter()
+            .map(|tx| tx.parent_id)
+            .collect::<HashSet<_>>();
+        let referenced = data
+            .transactions
+            .iter()
+            .filter(|tx| filter.transaction(tx) && !parents.contains(&tx.id))
+            .map(|tx| filter.detach(tx))
+            .filter(|tx| tx.to_account_id == 0)
+            .map(|tx| rules.apply(&tx).category_id.unwrap_or(tx.category_id))
+            .collect::<HashSet<_>>();
+        // system categories, and unknown ones when repaired
+        uncategorized = referenced
+            .iter()
+            .any(|id| is_system_category(*id) || !categories.iter().any(|c| c.id == *id));
+
+        flat.children
+            .retain(|c| referenced.contains(&c.category.id));
+        let parents = flat
+            .children
+            .iter()
+            .map(|c| c.parent.id)
+            .collect::<HashSet<_>>();
+        flat.parents.retain(|p| parents.contains(&p.id));
+    }
 
     // category parent
-    for item in categories.iter().filter(|m| {
-        m.right - m.left > 1
-            || !categories
-                .iter()
-                .any(|n| n.left < m.left && n.right > m.right)
-    }) {
+    for item in flat.parents.iter() {
         debug!("{}: {:?}\n", item.entity, item);
 
-        let category_type = match item.title.as_str() {
+        // group by the top-level category, so nested parents stay with their root
+        let root = tree.root(item.id).unwrap_or(item);
+        let category_type = match root.title.as_str() {
             "收入" => "2",
             _ => "3",
         };
@@ -391,279 +814,576 @@ pub fn migrate_categories(categories: &Vec<FinancistoCategory>) -> Result<Vec<St
         statements.push(format!(
             "INSERT INTO \"PARENTCATEGORYTABLE\" (\"parentCategoryTableID\", \"parentCategoryName\", \"categoryGroupID\", \"budgetAmountCategoryParent\", \"budgetCustomSetupParent\", \"budgetPeriodCategoryParent\", \"budgetEnabledCategoryParent\", \"categoryParentExtraColumnInt1\", \"categoryParentExtraColumnInt2\", \"categoryParentExtraColumnString1\", \"categoryParentExtraColumnString2\") VALUES ('{parentCategoryTableID}', '{parentCategoryName}', '{categoryGroupID}', NULL, NULL, NULL, '1', NULL, NULL, NULL, NULL);",
             parentCategoryTableID = item.id + 19,
-            parentCategoryName = item.title,
+            parentCategoryName = escape_quote(&item.title),
             categoryGroupID = category_type,
         ));
+    }
+
+    // category child
+    for item in flat.children.iter() {
+        debug!("{}: {:?}\n", item.category.entity, item.category);
+        debug!("parent = {:?}", item.parent);
 
         statements.push(format!(
             "INSERT INTO \"CHILDCATEGORYTABLE\" (\"categoryTableID\", \"childCategoryName\", \"parentCategoryID\", \"budgetAmount\", \"budgetCustomSetup\", \"budgetPeriod\", \"budgetEnabledCategoryChild\", \"childCategoryIcon\", \"categorySelectorVisibility\", \"categoryExtraColumnInt1\", \"categoryExtraColumnInt2\", \"categoryExtraColumnString1\", \"categoryExtraColumnString2\") VALUES ('{categoryTableID}', '{childCategoryName}', '{parentCategoryID}', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);",
-            categoryTableID = item.id + 19,
-            childCategoryName = item.title,
-            parentCategoryID = item.id + 19,
+            categoryTableID = item.category.id + 19,
+            childCategoryName = escape_quote(&item.name),
+            parentCategoryID = item.parent.id + 19,
         ));
     }
 
-    // category child
-    for item in categories.iter().filter(|m| {
-        m.right - m.left == 1
-            && categories
-                .iter()
-                .any(|n| n.left < m.left && n.right > m.right)
-    }) {
-        debug!("{}: {:?}\n", item.entity, item);
-
-        // find parent
-        let parent = categories
-            .iter()
-            .filter(|m| {
-                m.right - m.left > 1
-                    || !categories
-                        .iter()
-                        .any(|n| n.left < m.left && n.right > m.right)
-            })
-            .filter(|m| m.left < item.left && m.right > item.right)
-            .reduce(|a, _| a)
-            .unwrap();
-
-        debug!("parent = {:?}", parent);
+    // uncategorized, for transactions without a category in Financisto
+    let ids = CategoryIds::new(&categories);
+    let uncategorized_ids = if uncategorized {
+        vec![
+            (ids.uncategorized_income, "2"),
+            (ids.uncategorized_expense, "3"),
+        ]
+    } else {
+        vec![]
+    };
+    for (category_id, category_type) in uncategorized_ids {
+        statements.push(format!(
+            "INSERT INTO \"PARENTCATEGORYTABLE\" (\"parentCategoryTableID\", \"parentCategoryName\", \"categoryGroupID\", \"budgetAmountCategoryParent\", \"budgetCustomSetupParent\", \"budgetPeriodCategoryParent\", \"budgetEnabledCategoryParent\", \"categoryParentExtraColumnInt1\", \"categoryParentExtraColumnInt2\", \"categoryParentExtraColumnString1\", \"categoryParentExtraColumnString2\") VALUES ('{parentCategoryTableID}', '{parentCategoryName}', '{categoryGroupID}', NULL, NULL, NULL, '1', NULL, NULL, NULL, NULL);",
+            parentCategoryTableID = category_id,
+            parentCategoryName = UNCATEGORIZED_NAME,
+            categoryGroupID = category_type,
+        ));
 
         statements.push(format!(
             "INSERT INTO \"CHILDCATEGORYTABLE\" (\"categoryTableID\", \"childCategoryName\", \"parentCategoryID\", \"budgetAmount\", \"budgetCustomSetup\", \"budgetPeriod\", \"budgetEnabledCategoryChild\", \"childCategoryIcon\", \"categorySelectorVisibility\", \"categoryExtraColumnInt1\", \"categoryExtraColumnInt2\", \"categoryExtraColumnString1\", \"categoryExtraColumnString2\") VALUES ('{categoryTableID}', '{childCategoryName}', '{parentCategoryID}', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);",
-            categoryTableID = item.id + 19,
-            childCategoryName = item.title,
-            parentCategoryID = parent.id + 19,
+            categoryTableID = category_id,
+            childCategoryName = UNCATEGORIZED_NAME,
+            parentCategoryID = category_id,
         ));
     }
 
     Ok(statements)
 }
 
-pub fn migrate_transactions(
-    transactions: &Vec<FinancistoTransaction>,
-    currencies: &Vec<FinancistoCurrency>,
-) -> Result<Vec<String>> {
+/// Bluecoins items created while migrating transactions, keyed by name.
+struct Items {
+    last_id: i64,
+    ids: HashMap<String, i64>,
+}
+
+impl Items {
+    fn new() -> Self {
+        Self {
+            last_id: 40,
+            ids: HashMap::new(),
+        }
+    }
+
+    /// ID of the named item, inserting it into ITEMTABLE on first use.
+    fn id(&mut self, name: &str, statements: &mut Vec<String>) -> i64 {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        self.last_id += 1;
+        self.ids.insert(name.to_owned(), self.last_id);
+
+        statements.push(format!(
+            "INSERT INTO \"ITEMTABLE\" (\"itemTableID\", \"itemName\", \"itemAutoFillVisibility\") VALUES ('{}', '{}', '0');",
+            self.last_id,
+            escape_quote(name),
+        ));
+
+        self.last_id
+    }
+}
+
+/// Attach labels to a migrated transaction.
+fn push_labels(
+    statements: &mut Vec<String>,
+    last_label_id: &mut i64,
+    labels: &[String],
+    transaction_id: i64,
+) {
+    for label in labels.iter() {
+        *last_label_id += 1;
+
+        statements.push(format!(
+            "INSERT INTO \"LABELSTABLE\" (\"labelsTableID\", \"labelName\", \"transactionIDLabels\") VALUES ('{}', '{}', '{}');",
+            last_label_id,
+            escape_quote(label),
+            transaction_id,
+        ));
+    }
+}
+
+/// Both legs of a transfer, taking IDs `id` and `id + 1`. Item, currency, date, notes, status and
+/// reminder are taken from `base`.
+///
+/// Credit card payments take the same form: Bluecoins has no transaction type of its own for them,
+/// it logs a payment as a transfer into the card account, whose billing cycle is set on the
+/// account itself (see [`migrate_accounts`]). Only the item name tells them apart.
+fn transfer_legs(
+    tx: &FinancistoTransaction,
+    id: i64,
+    base: BluecoinsTransaction,
+) -> [BluecoinsTransaction; 2] {
+    [
+        // from account -> to account
+        BluecoinsTransaction {
+            transactions_table_id: id,
+            amount: tx.from_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
+            conversion_rate_new: 1.0,
+            transaction_type_id: TYPE_TRANSFER,
+            category_id: 3, // transfer
+            account_id: tx.from_account_id + 5,
+            account_reference: 1, // UNKNOW meanings
+            account_pair_id: tx.to_account_id + 5,
+            uid_pair_id: id + 1, // pair to transactionsTableID
+            deleted_transaction: 6,
+            new_split_transaction_id: 0,
+            transfer_group_id: id,
+            ..base.clone()
+        },
+        // to account -> from account
+        BluecoinsTransaction {
+            transactions_table_id: id + 1,
+            amount: tx.to_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
+            conversion_rate_new: 1.0,
+            transaction_type_id: TYPE_TRANSFER,
+            category_id: 3, // transfer
+            account_id: tx.to_account_id + 5,
+            account_reference: 2, // UNKNOW meanings
+            account_pair_id: tx.from_account_id + 5,
+            uid_pair_id: id, // pair to transactionsTableID
+            deleted_transaction: 6,
+            new_split_transaction_id: 0,
+            transfer_group_id: id,
+            ..base
+        },
+    ]
+}
+
+/// Notes of a split line, combined with the note of its parent.
+fn split_notes(split_note: SplitNote, parent: Option<&str>, child: Option<&str>) -> String {
+    let parent = parent.filter(|n| !n.is_empty());
+    let child = child.filter(|n| !n.is_empty());
+
+    match (split_note, parent, child) {
+        (SplitNote::Notes, Some(parent), Some(child)) => format!("{} / {}", parent, child),
+        (SplitNote::Notes, Some(parent), None) => parent.to_owned(),
+        (_, _, child) => child.unwrap_or_default().to_owned(),
+    }
+}
+
+pub fn migrate_transactions(data: &Financisto, options: &MigrateOptions) -> Result<Vec<String>> {
+    check_ranges(data)?;
+    let transactions = &data.transactions;
+    let index = data.index();
+    let category_ids = CategoryIds::new(&data.categories);
+    let rules = options.rules.resolve(data)?;
+    let account_titles = data
+        .accounts
+        .iter()
+        .map(|a| (a.id, a.title.as_str()))
+        .collect::<HashMap<_, _>>();
+    for issue in check_credit_card_payments(data) {
+        warn!("{}", issue);
+    }
+    let issues = check_integrity(data);
+    for issue in issues.iter() {
+        warn!("{}", issue);
+    }
+    if options.integrity == Integrity::Strict && !issues.is_empty() {
+        let messages = issues.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        return Err(messages.join("\n").into());
+    }
+    let (orphans, skipped) = repairs(&issues, options.integrity);
+    let transfer_item = |tx: &FinancistoTransaction, outcome: &RuleOutcome| {
+        outcome.item.clone().unwrap_or_else(|| {
+            let from = account_titles.get(&tx.from_account_id).unwrap_or(&"");
+            let to = account_titles.get(&tx.to_account_id).unwrap_or(&"");
+            if tx.is_ccard_payment == 1 {
+                options.item_names.credit_card_payment_name(from, to)
+            } else {
+                options.item_names.transfer_name(from, to)
+            }
+        })
+    };
+    let mut unknown_categories = Vec::new();
+    let mut unsupported_recurrences = Vec::new();
+    let mut unknown_status = HashSet::new();
+    let mut status_of = |tx: &FinancistoTransaction| {
+        let code = tx.status.as_deref();
+        if let Some(code) = code.filter(|c| !c.is_empty() && !options.status.codes.contains_key(*c))
+        {
+            if unknown_status.insert(code.to_owned()) {
+                warn!(
+                    "unknown transaction status '{}', mapped to {}",
+                    code, options.status.unknown
+                );
+            }
+        }
+        options.status.resolve(code)
+    };
+    let filter = options.filter.resolve(data, options.timezone)?;
     let mut statements = Vec::new();
-    let mut last_item_id: i64 = 40;
-    let mut items: HashMap<String, i64> = HashMap::new();
+    let mut items = Items::new();
+    let mut last_label_id: i64 = 0;
     let mut seq = 1;
     let mut id_set: HashSet<i64> = HashSet::new();
+    // the opening balances of `migrate_accounts` have the IDs of their accounts
+    let mut used_ids: HashSet<i64> = data.accounts.iter().map(|a| a.id as i64 + 5).collect();
 
-    for tx in transactions {
+    for tx in first_of_ids("transactions", transactions, |t| t.id, options.integrity)? {
         debug!("{:?}", &tx);
 
-        // ignore template
-        if tx.is_template == 1 {
+        if tx.parent_id != 0 && !orphans.contains(&tx.id) {
+            // split items, handled by header
+            continue;
+        }
+        if skipped.contains(&tx.id) || !filter.transaction(tx) {
             continue;
         }
 
-        let currency =
-            if let Some(currency) = currencies.iter().find(|c| c.id == tx.original_currency_id) {
-                &currency.name
-            } else {
-                "TWD"
-            };
-        let mut notes = "";
-        let mut tx_time_in_milli: i64 = tx.datetime;
-        let tx_time = chrono::Local
-            .timestamp_millis_opt(tx_time_in_milli)
-            .unwrap();
+        let split_lines = index
+            .split_lines(tx.id)
+            .iter()
+            .copied()
+            .filter(|t| !orphans.contains(&t.id))
+            .filter(|t| !skipped.contains(&t.id))
+            .collect::<Vec<_>>();
+        let mut split_children = split_lines
+            .iter()
+            .copied()
+            .filter(|t| filter.split_line(t))
+            .collect::<Vec<_>>();
+        if !split_lines.is_empty() && split_children.is_empty() {
+            // every line is filtered out
+            continue;
+        }
 
-        if tx.to_account_id == 0 {
-            // income or payment
-            let item_id: i64 = if let Some(value) = &tx.note {
-                if items.contains_key(value) {
-                    *items.get(value).unwrap() as i64
-                } else if tx.from_account_id == 33 && value.parse::<i32>().is_ok() {
-                    // lottery ticket number
-                    notes = value;
-
-                    let item_name = "運動彩券";
-                    if items.contains_key(item_name) {
-                        *items.get(item_name).unwrap() as i64
-                    } else {
-                        last_item_id = last_item_id + 1;
-                        items.insert(item_name.to_owned(), last_item_id);
-
-                        statements.push(format!(
-                            "INSERT INTO \"ITEMTABLE\" (\"itemTableID\", \"itemName\", \"itemAutoFillVisibility\") VALUES ('{}', '{}', '0');",
-                            last_item_id,
-                            escape_quote(item_name),
-                        ));
-
-                        last_item_id
-                    }
+        // templates are only migrated when scheduled, as Bluecoins reminders
+        let recurrence = if tx.is_template != 0 && !orphans.contains(&tx.id) {
+            match Recurrence::of(tx, options.timezone) {
+                Ok(Some(recurrence)) => Some(recurrence),
+                Ok(None) => continue,
+                Err(e) if options.integrity == Integrity::Repair => {
+                    warn!("skip scheduled transaction {}: {}", tx.id, e);
+                    continue;
+                }
+                Err(e) => {
+                    unsupported_recurrences.push(format!("scheduled transaction {}: {}", tx.id, e));
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+        let reminder = |group_id: i64| recurrence.as_ref().map(|r| r.reminder(group_id));
+        // a reminder without a note is named after its template
+        let named;
+        let tx = match &tx.template_name {
+            Some(name)
+                if !name.is_empty()
+                    && recurrence.is_some()
+                    && tx.note.as_deref().unwrap_or("").is_empty() =>
+            {
+                named = FinancistoTransaction {
+                    note: Some(name.to_owned()),
+                    ..tx.clone()
+                };
+                &named
+            }
+            _ => tx,
+        };
+
+        let currency = if let Some(currency) = index.currency(tx.original_currency_id) {
+            &currency.name
+        } else {
+            "TWD"
+        };
+        let mut tx_time_in_milli: i64 = tx.datetime;
+        let date = options.timezone.format(tx_time_in_milli)?;
+
+        let mut outcome = rules.apply(tx);
+        let notes = outcome
+            .notes
+            .clone()
+            .or_else(|| {
+                // the note of single transactions is already their item name
+                if tx.to_account_id != 0 {
+                    tx.note.clone()
                 } else {
-                    last_item_id = last_item_id + 1;
-                    items.insert(value.to_owned(), last_item_id);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        let status = status_of(tx);
 
-                    statements.push(format!(
-                        "INSERT INTO \"ITEMTABLE\" (\"itemTableID\", \"itemName\", \"itemAutoFillVisibility\") VALUES ('{}', '{}', '0');",
-                        last_item_id,
-                        escape_quote(value),
-                    ));
+        // transfer with the other side filtered out
+        let detached = filter.detach(tx);
+        if detached.to_account_id != tx.to_account_id && outcome.item.is_none() {
+            outcome.item = Some(transfer_item(tx, &outcome));
+        }
+        let tx = detached.as_ref();
 
-                    last_item_id
+        if tx.to_account_id == 0 {
+            // income or payment
+            let item_id: i64 = if let Some(name) = &outcome.item {
+                items.id(name, &mut statements)
+            } else if let Some(value) = &tx.note {
+                items.id(value, &mut statements)
+            } else if tx.from_amount >= 0 {
+                match &options.item_names.unnamed_income {
+                    Some(name) => items.id(name, &mut statements),
+                    None => 3, // Unnamed Income
                 }
             } else {
-                if tx.from_amount >= 0 {
-                    3 // Unnamed Income
-                } else {
-                    2 // Unnamed Expense
+                match &options.item_names.unnamed_expense {
+                    Some(name) => items.id(name, &mut statements),
+                    None => 2, // Unnamed Expense
                 }
             };
 
-            if tx.parent_id == 0 {
-                // single or split head
-                id_set.insert(tx_time_in_milli);
+            let mut category_id =
+                |tx: &FinancistoTransaction, outcome: &RuleOutcome, income: bool| {
+                    let category_id = outcome.category_id.unwrap_or(tx.category_id);
+                    category_ids
+                        .resolve(category_id, income)
+                        .or_else(|| {
+                            if options.integrity == Integrity::Repair {
+                                category_ids.resolve(NO_CATEGORY_ID, income)
+                            } else {
+                                None
+                            }
+                        })
+                        .unwrap_or_else(|| {
+                            unknown_categories.push(format!(
+                                "transaction {} refers to unknown category {}",
+                                tx.id, category_id
+                            ));
+                            0
+                        })
+                };
 
-                let mut split_children = transactions
-                    .iter()
-                    .filter(|t| t.parent_id == tx.id)
-                    .collect::<Vec<_>>();
+            // single or split head
+            id_set.insert(tx_time_in_milli);
 
-                if split_children.is_empty() {
-                    // single
+            if split_children.is_empty() {
+                // single
 
-                    if id_set.contains(&tx_time_in_milli) {
-                        // warn!("conflict id: {:?}", tx);
-                        tx_time_in_milli = tx_time_in_milli + seq;
-                        seq = seq + 1;
-                    }
-
-                    statements.push(format!(
-                        "INSERT INTO \"TRANSACTIONSTABLE\" (\"transactionsTableID\", \"itemID\", \"amount\", \"transactionCurrency\", \"conversionRateNew\", \"date\", \"transactionTypeID\", \"categoryID\", \"accountID\", \"notes\", \"status\", \"accountReference\", \"accountPairID\", \"uidPairID\", \"deletedTransaction\", \"newSplitTransactionID\", \"transferGroupID\", \"reminderTransaction\", \"reminderGroupID\", \"reminderFrequency\", \"reminderRepeatEvery\", \"reminderEndingType\", \"reminderStartDate\", \"reminderEndDate\", \"reminderAfterNoOfOccurences\", \"reminderAutomaticLogTransaction\", \"reminderRepeatByDayOfMonth\", \"reminderExcludeWeekend\", \"reminderWeekDayMoveSetting\", \"reminderUnbilled\", \"creditCardInstallment\", \"reminderVersion\", \"dataExtraColumnString1\") VALUES ('{transactionsTableID}', '{itemID}', '{amount}', '{transactionCurrency}', '{conversionRateNew}', '{date}', '{transactionTypeID}', '{categoryID}', '{accountID}', '{notes}', '{status}', '{accountReference}', '{accountPairID}', '{uidPairID}', '{deletedTransaction}', '{newSplitTransactionID}', '{transferGroupID}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);",
-                        transactionsTableID = tx_time_in_milli,
-                        itemID = item_id,
-                        amount = tx.from_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
-                        transactionCurrency = currency,
-                        conversionRateNew = if tx.original_from_amount != 0 { (tx.original_from_amount as f64) / (tx.from_amount as f64) } else { 1.0 },
-                        date = tx_time.format("%Y-%m-%d %H:%M:%S"),
-                        transactionTypeID = if tx.from_amount >= 0 { 4 } else { 3 }, // 3 = expense, 4 = income
-                        categoryID = tx.category_id + 19,
-                        accountID = tx.from_account_id + 5,
-                        notes = notes,
-                        status = 0,
-                        accountReference = 1, // UNKNOW meanings
-                        accountPairID = tx.from_account_id + 5,
-                        uidPairID = tx_time_in_milli,
-                        deletedTransaction = 6,
-                        newSplitTransactionID = 0,
-                        transferGroupID = 0
-                    ));
-                } else {
-                    if id_set.contains(&tx_time_in_milli) {
-                        // warn!("conflict id: {:?}", tx);
-                        tx_time_in_milli = tx_time_in_milli + seq;
-                        seq = seq + split_children.len() as i64;
+                if id_set.contains(&tx_time_in_milli) {
+                    // warn!("conflict id: {:?}", tx);
+                    tx_time_in_milli += seq;
+                    seq += 1;
+                }
+                tx_time_in_milli = take_ids(&mut used_ids, tx_time_in_milli, 1);
+
+                statements.push(
+                    BluecoinsTransaction {
+                        transactions_table_id: tx_time_in_milli,
+                        item_id,
+                        amount: tx.from_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
+                        transaction_currency: currency.to_owned(),
+                        conversion_rate_new: if tx.original_from_amount != 0 {
+                            (tx.original_from_amount as f64) / (tx.from_amount as f64)
+                        } else {
+                            1.0
+                        },
+                        date: date.to_owned(),
+                        transaction_type_id: if tx.from_amount >= 0 {
+                            TYPE_INCOME
+                        } else {
+                            TYPE_EXPENSE
+                        },
+                        category_id: category_id(tx, &outcome, tx.from_amount >= 0),
+                        account_id: tx.from_account_id + 5,
+                        notes: notes.to_owned(),
+                        status,
+                        account_reference: 1, // UNKNOW meanings
+                        account_pair_id: tx.from_account_id + 5,
+                        uid_pair_id: tx_time_in_milli,
+                        deleted_transaction: 6,
+                        new_split_transaction_id: 0,
+                        transfer_group_id: 0,
+                        reminder: reminder(tx_time_in_milli),
                     }
-
-                    // split header
-                    split_children.sort_by(|a, b| a.datetime.partial_cmp(&b.datetime).unwrap());
-
-                    for (index, child) in split_children.iter().enumerate() {
-                        let currency = if let Some(currency) = currencies
-                            .iter()
-                            .find(|c| c.id == child.original_currency_id)
-                        {
+                    .to_sql(),
+                );
+
+                push_labels(
+                    &mut statements,
+                    &mut last_label_id,
+                    &outcome.labels,
+                    tx_time_in_milli,
+                );
+            } else {
+                // transfer lines take two IDs
+                let line_ids = split_children
+                    .iter()
+                    .map(|c| {
+                        if filter.detach(c).to_account_id != 0 {
+                            2
+                        } else {
+                            1
+                        }
+                    })
+                    .sum::<i64>();
+                if id_set.contains(&tx_time_in_milli) {
+                    // warn!("conflict id: {:?}", tx);
+                    tx_time_in_milli += seq;
+                    seq += line_ids;
+                }
+                // the ID of the split itself is only referred to by its lines
+                tx_time_in_milli = take_ids(&mut used_ids, tx_time_in_milli, 1 + line_ids);
+
+                // split header
+                split_children.sort_by(|a, b| a.datetime.partial_cmp(&b.datetime).unwrap());
+
+                let mut next_id = tx_time_in_milli + 1;
+                for line in split_children.iter().copied() {
+                    let child = filter.detach(line);
+                    let child = child.as_ref();
+                    let currency =
+                        if let Some(currency) = index.currency(child.original_currency_id) {
                             &currency.name
                         } else {
                             "TWD"
                         };
+                    let mut child_outcome = rules.apply(line);
+                    if child.to_account_id != line.to_account_id && child_outcome.item.is_none() {
+                        child_outcome.item = Some(transfer_item(line, &child_outcome));
+                    }
+                    for label in outcome.labels.iter() {
+                        if !child_outcome.labels.contains(label) {
+                            child_outcome.labels.push(label.to_owned());
+                        }
+                    }
+                    // split lines usually carry no status of their own
+                    let child_status = match child.status.as_deref() {
+                        None | Some("") => status,
+                        Some(_) => status_of(child),
+                    };
+                    let child_notes = split_notes(
+                        options.split_note,
+                        outcome.notes.as_deref().or(tx.note.as_deref()),
+                        child_outcome.notes.as_deref().or(child.note.as_deref()),
+                    );
+
+                    if child.to_account_id != 0 {
+                        // transfer line, Bluecoins can not split transfers so it becomes a plain transfer
+                        let item_id =
+                            items.id(&transfer_item(child, &child_outcome), &mut statements);
+                        let legs = transfer_legs(
+                            child,
+                            next_id,
+                            BluecoinsTransaction {
+                                item_id,
+                                transaction_currency: currency.to_owned(),
+                                date: date.to_owned(),
+                                notes: child_notes,
+                                status: child_status,
+                                reminder: reminder(tx_time_in_milli),
+                                ..Default::default()
+                            },
+                        );
+
+                        for leg in legs.iter() {
+                            statements.push(leg.to_sql());
+                            push_labels(
+                                &mut statements,
+                                &mut last_label_id,
+                                &child_outcome.labels,
+                                leg.transactions_table_id,
+                            );
+                        }
 
-                        statements.push(format!(
-                            "INSERT INTO \"TRANSACTIONSTABLE\" (\"transactionsTableID\", \"itemID\", \"amount\", \"transactionCurrency\", \"conversionRateNew\", \"date\", \"transactionTypeID\", \"categoryID\", \"accountID\", \"notes\", \"status\", \"accountReference\", \"accountPairID\", \"uidPairID\", \"deletedTransaction\", \"newSplitTransactionID\", \"transferGroupID\", \"reminderTransaction\", \"reminderGroupID\", \"reminderFrequency\", \"reminderRepeatEvery\", \"reminderEndingType\", \"reminderStartDate\", \"reminderEndDate\", \"reminderAfterNoOfOccurences\", \"reminderAutomaticLogTransaction\", \"reminderRepeatByDayOfMonth\", \"reminderExcludeWeekend\", \"reminderWeekDayMoveSetting\", \"reminderUnbilled\", \"creditCardInstallment\", \"reminderVersion\", \"dataExtraColumnString1\") VALUES ('{transactionsTableID}', '{itemID}', '{amount}', '{transactionCurrency}', '{conversionRateNew}', '{date}', '{transactionTypeID}', '{categoryID}', '{accountID}', '{notes}', '{status}', '{accountReference}', '{accountPairID}', '{uidPairID}', '{deletedTransaction}', '{newSplitTransactionID}', '{transferGroupID}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);",
-                            transactionsTableID = tx_time_in_milli + index as i64 + 1,
-                            itemID = item_id,
-                            amount = child.from_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
-                            transactionCurrency = currency,
-                            conversionRateNew = 1.0,
-                            date = tx_time.format("%Y-%m-%d %H:%M:%S"),
-                            transactionTypeID = if tx.from_amount >= 0 { 4 } else { 3 }, // 3 = expense, 4 = income
-                            categoryID = child.category_id + 19,
-                            accountID = child.from_account_id + 5,
-                            notes = child.note.as_ref().unwrap_or(&String::new()),
-                            status = 0,
-                            accountReference = 1, // UNKNOW meanings
-                            accountPairID = child.from_account_id + 5,
-                            uidPairID = tx_time_in_milli + index as i64 + 1,
-                            deletedTransaction = 6,
-                            newSplitTransactionID = tx_time_in_milli,
-                            transferGroupID = 0
-                        ));
+                        next_id += 2;
+                        continue;
                     }
+
+                    let item_id = match &child_outcome.item {
+                        Some(name) => items.id(name, &mut statements),
+                        None => item_id,
+                    };
+                    // typed by its own sign, a refund inside an expense split stays an income
+                    let income = child.from_amount >= 0;
+
+                    statements.push(
+                        BluecoinsTransaction {
+                            transactions_table_id: next_id,
+                            item_id,
+                            amount: child.from_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
+                            transaction_currency: currency.to_owned(),
+                            conversion_rate_new: 1.0,
+                            date: date.to_owned(),
+                            transaction_type_id: if income { TYPE_INCOME } else { TYPE_EXPENSE },
+                            category_id: category_id(child, &child_outcome, income),
+                            account_id: child.from_account_id + 5,
+                            notes: child_notes,
+                            status: child_status,
+                            account_reference: 1, // UNKNOW meanings
+                            account_pair_id: child.from_account_id + 5,
+                            uid_pair_id: next_id,
+                            deleted_transaction: 6,
+                            new_split_transaction_id: tx_time_in_milli,
+                            transfer_group_id: 0,
+                            reminder: reminder(tx_time_in_milli),
+                        }
+                        .to_sql(),
+                    );
+
+                    push_labels(
+                        &mut statements,
+                        &mut last_label_id,
+                        &child_outcome.labels,
+                        next_id,
+                    );
+
+                    next_id += 1;
                 }
-            } else {
-                // split items, handled by header
             }
         } else {
-            // transfer
-            let name = "轉帳";
-            let item_id = if items.contains_key(name) {
-                *items.get(name).unwrap()
-            } else {
-                last_item_id = last_item_id + 1;
-                items.insert(name.to_owned(), last_item_id);
-
-                statements.push(format!(
-                    "INSERT INTO \"ITEMTABLE\" (\"itemTableID\", \"itemName\", \"itemAutoFillVisibility\") VALUES ('{}', '{}', '0');",
-                    last_item_id,
-                    escape_quote(name),
-                ));
-
-                last_item_id
-            };
+            // transfer, or credit card payment which Bluecoins logs as a transfer to the card
+            let item_id = items.id(&transfer_item(tx, &outcome), &mut statements);
 
             if id_set.contains(&tx_time_in_milli) {
                 // warn!("conflict id: {:?}", tx);
-                tx_time_in_milli = tx_time_in_milli + seq;
-                seq = seq + 2;
+                tx_time_in_milli += seq;
+                seq += 2;
             }
             id_set.insert(tx_time_in_milli);
+            tx_time_in_milli = take_ids(&mut used_ids, tx_time_in_milli, 2);
+
+            let legs = transfer_legs(
+                tx,
+                tx_time_in_milli,
+                BluecoinsTransaction {
+                    item_id,
+                    transaction_currency: currency.to_owned(),
+                    date: date.to_owned(),
+                    notes: notes.to_owned(),
+                    status,
+                    reminder: reminder(tx_time_in_milli),
+                    ..Default::default()
+                },
+            );
+
+            for leg in legs.iter() {
+                statements.push(leg.to_sql());
+                push_labels(
+                    &mut statements,
+                    &mut last_label_id,
+                    &outcome.labels,
+                    leg.transactions_table_id,
+                );
+            }
+        }
+    }
 
-            // from account -> to account
-            statements.push(format!(
-                "INSERT INTO \"TRANSACTIONSTABLE\" (\"transactionsTableID\", \"itemID\", \"amount\", \"transactionCurrency\", \"conversionRateNew\", \"date\", \"transactionTypeID\", \"categoryID\", \"accountID\", \"notes\", \"status\", \"accountReference\", \"accountPairID\", \"uidPairID\", \"deletedTransaction\", \"newSplitTransactionID\", \"transferGroupID\", \"reminderTransaction\", \"reminderGroupID\", \"reminderFrequency\", \"reminderRepeatEvery\", \"reminderEndingType\", \"reminderStartDate\", \"reminderEndDate\", \"reminderAfterNoOfOccurences\", \"reminderAutomaticLogTransaction\", \"reminderRepeatByDayOfMonth\", \"reminderExcludeWeekend\", \"reminderWeekDayMoveSetting\", \"reminderUnbilled\", \"creditCardInstallment\", \"reminderVersion\", \"dataExtraColumnString1\") VALUES ('{transactionsTableID}', '{itemID}', '{amount}', '{transactionCurrency}', '{conversionRateNew}', '{date}', '{transactionTypeID}', '{categoryID}', '{accountID}', '{notes}', '{status}', '{accountReference}', '{accountPairID}', '{uidPairID}', '{deletedTransaction}', '{newSplitTransactionID}', '{transferGroupID}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);",
-                transactionsTableID = tx_time_in_milli,
-                itemID = item_id,
-                amount = tx.from_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
-                transactionCurrency = currency,
-                conversionRateNew = 1.0,
-                date = tx_time.format("%Y-%m-%d %H:%M:%S"),
-                transactionTypeID = 5, // transfer
-                categoryID = 3, // transfer
-                accountID = tx.from_account_id + 5,
-                notes = notes,
-                status = 0,
-                accountReference = 1, // UNKNOW meanings
-                accountPairID = tx.to_account_id + 5,
-                uidPairID = tx_time_in_milli + 1, // pair to transactionsTableID
-                deletedTransaction = 6,
-                newSplitTransactionID = 0,
-                transferGroupID = tx_time_in_milli
-            ));
-
-            // to account -> from account
-            statements.push(format!(
-                "INSERT INTO \"TRANSACTIONSTABLE\" (\"transactionsTableID\", \"itemID\", \"amount\", \"transactionCurrency\", \"conversionRateNew\", \"date\", \"transactionTypeID\", \"categoryID\", \"accountID\", \"notes\", \"status\", \"accountReference\", \"accountPairID\", \"uidPairID\", \"deletedTransaction\", \"newSplitTransactionID\", \"transferGroupID\", \"reminderTransaction\", \"reminderGroupID\", \"reminderFrequency\", \"reminderRepeatEvery\", \"reminderEndingType\", \"reminderStartDate\", \"reminderEndDate\", \"reminderAfterNoOfOccurences\", \"reminderAutomaticLogTransaction\", \"reminderRepeatByDayOfMonth\", \"reminderExcludeWeekend\", \"reminderWeekDayMoveSetting\", \"reminderUnbilled\", \"creditCardInstallment\", \"reminderVersion\", \"dataExtraColumnString1\") VALUES ('{transactionsTableID}', '{itemID}', '{amount}', '{transactionCurrency}', '{conversionRateNew}', '{date}', '{transactionTypeID}', '{categoryID}', '{accountID}', '{notes}', '{status}', '{accountReference}', '{accountPairID}', '{uidPairID}', '{deletedTransaction}', '{newSplitTransactionID}', '{transferGroupID}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);",
-                transactionsTableID = tx_time_in_milli + 1,
-                itemID = item_id,
-                amount = tx.to_amount * 1000000 / 100, // 2 digit fromn financisto, 6 digit from bluecoins
-                transactionCurrency = currency,
-                conversionRateNew = 1.0,
-                date = tx_time.format("%Y-%m-%d %H:%M:%S"),
-                transactionTypeID = 5, // transfer
-                categoryID = 3, // transfer
-                accountID = tx.to_account_id + 5,
-                notes = notes,
-                status = 0,
-                accountReference = 2, // UNKNOW meanings
-                accountPairID = tx.from_account_id + 5,
-                uidPairID = tx_time_in_milli, // pair to transactionsTableID
-                deletedTransaction = 6,
-                newSplitTransactionID = 0,
-                transferGroupID = tx_time_in_milli
-            ));
+    unknown_categories.append(&mut unsupported_recurrences);
+    if !unknown_categories.is_empty() {
+        for message in unknown_categories.iter() {
+            error!("{}", message);
         }
+        return Err(unknown_categories.join("\n").into());
     }
 
     Ok(statements)
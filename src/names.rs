@@ -0,0 +1,50 @@
+/// Names of the Bluecoins items created for transactions that have no note of their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemNames {
+    /// Item of transfers, `{from}` and `{to}` are replaced by the account titles.
+    pub transfer: String,
+    /// Item of transfers flagged as credit card payments, `{from}` and `{to}` are replaced by the
+    /// account titles.
+    pub credit_card_payment: String,
+    /// Item of expenses without a note, Bluecoins' built-in "Unnamed Expense" if `None`.
+    pub unnamed_expense: Option<String>,
+    /// Item of incomes without a note, Bluecoins' built-in "Unnamed Income" if `None`.
+    pub unnamed_income: Option<String>,
+}
+
+impl Default for ItemNames {
+    fn default() -> Self {
+        Self::for_language("zh-TW")
+    }
+}
+
+impl ItemNames {
+    /// Built-in names for the language of the Bluecoins install, English if unknown.
+    pub fn for_language(language: &str) -> Self {
+        let (transfer, credit_card_payment) = match language.to_lowercase().as_str() {
+            "zh-tw" | "zh-hant" | "zh-hk" => ("轉帳", "信用卡繳款"),
+            "zh-cn" | "zh-hans" | "zh" => ("转账", "信用卡还款"),
+            "ja" => ("振替", "クレジットカード支払い"),
+            _ => ("Transfer", "Credit card payment"),
+        };
+
+        Self {
+            transfer: transfer.to_owned(),
+            credit_card_payment: credit_card_payment.to_owned(),
+            unnamed_expense: None,
+            unnamed_income: None,
+        }
+    }
+
+    /// Item name of a transfer between the two accounts.
+    pub fn transfer_name(&self, from: &str, to: &str) -> String {
+        self.transfer.replace("{from}", from).replace("{to}", to)
+    }
+
+    /// Item name of a credit card payment between the two accounts.
+    pub fn credit_card_payment_name(&self, from: &str, to: &str) -> String {
+        self.credit_card_payment
+            .replace("{from}", from)
+            .replace("{to}", to)
+    }
+}
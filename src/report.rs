@@ -0,0 +1,405 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{
+    CategoryTree, Filter, Financisto, Insert, MigrateOptions, Result, Selection, Timezone,
+    TYPE_EXPENSE, TYPE_INCOME, TYPE_TRANSFER,
+};
+
+/// Money moved in an account, in cents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    /// Amount of the init transaction Bluecoins opens the account with.
+    pub opening: i64,
+    pub income: i64,
+    pub expense: i64,
+    pub transfer_in: i64,
+    pub transfer_out: i64,
+    pub balance: i64,
+}
+
+impl Totals {
+    fn close(&mut self) {
+        self.balance =
+            self.opening + self.income + self.expense + self.transfer_in + self.transfer_out;
+    }
+}
+
+/// Totals of one account, from the Financisto input and from the generated Bluecoins rows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccountSummary {
+    /// Financisto account ID.
+    pub id: i32,
+    pub title: String,
+    pub financisto: Totals,
+    pub bluecoins: Totals,
+    /// Balance Financisto keeps for the account, only for a migration of the whole book.
+    pub stored_balance: Option<i64>,
+    pub mismatch: bool,
+}
+
+impl AccountSummary {
+    fn new(id: i32, title: &str) -> Self {
+        Self {
+            id,
+            title: title.to_owned(),
+            financisto: Totals::default(),
+            bluecoins: Totals::default(),
+            stored_balance: None,
+            mismatch: false,
+        }
+    }
+}
+
+/// What a migration read and wrote, to tell whether it went right.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// Number of Financisto entities, by kind.
+    pub financisto: BTreeMap<String, usize>,
+    /// Number of generated rows, by Bluecoins table.
+    pub bluecoins: BTreeMap<String, usize>,
+    pub accounts: Vec<AccountSummary>,
+}
+
+impl Report {
+    /// Summarize the Financisto data and the statements migrated from it with the options.
+    pub fn new(data: &Financisto, options: &MigrateOptions, statements: &[String]) -> Result<Self> {
+        let mut financisto = BTreeMap::new();
+        financisto.insert("currencies".to_owned(), data.currencies.len());
+        financisto.insert("accounts".to_owned(), data.accounts.len());
+        financisto.insert("categories".to_owned(), data.categories.len());
+        financisto.insert("payees".to_owned(), data.payees.len());
+        let templates = data.transactions.iter().filter(|t| t.is_template != 0);
+        financisto.insert("templates".to_owned(), templates.count());
+        financisto.insert(
+            "transactions".to_owned(),
+            data.transactions.len() - financisto["templates"],
+        );
+
+        // the Financisto side only reads the data and the filter as documented, none of the
+        // migration, so that its mistakes show up as mismatches
+        let selection = FilterRange::new(&options.filter, options.timezone)?;
+        let titles = data
+            .accounts
+            .iter()
+            .map(|a| (a.id, a.title.as_str()))
+            .collect::<HashMap<_, _>>();
+        let selected = |id: i32| match titles.get(&id) {
+            Some(title) => selects(&options.filter.accounts, id, &[title]),
+            // unknown accounts only when the accounts are not limited
+            None => options.filter.accounts.is_empty(),
+        };
+        let tree = CategoryTree::new(&data.categories);
+        let picked = |id: i32| {
+            let path = tree.path(id);
+            // unknown categories only when the categories are not limited
+            if path.is_empty() {
+                return options.filter.categories.is_empty();
+            }
+            let names = path
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    let full = path[..=i]
+                        .iter()
+                        .map(|p| p.title.as_str())
+                        .collect::<Vec<_>>();
+                    (c.id, [c.title.to_owned(), full.join(":")])
+                })
+                .collect::<Vec<_>>();
+            let named = |names_of: &[String], (id, titles): &(i32, [String; 2])| {
+                names_of.contains(&id.to_string()) || titles.iter().any(|t| names_of.contains(t))
+            };
+            let include = &options.filter.categories.include;
+            (include.is_empty() || names.iter().any(|n| named(include, n)))
+                && !names
+                    .iter()
+                    .any(|n| named(&options.filter.categories.exclude, n))
+        };
+
+        let mut accounts = data
+            .accounts
+            .iter()
+            .filter(|a| selected(a.id))
+            .map(|a| {
+                let mut summary = AccountSummary::new(a.id, &a.title);
+                // only a whole book adds up to the balance Financisto keeps
+                if options.filter.is_empty() {
+                    summary.stored_balance = Some(a.total_amount);
+                }
+                (a.id, summary)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let index = data.index();
+        // splits are counted through their lines
+        let parents = data
+            .transactions
+            .iter()
+            .map(|t| t.parent_id)
+            .collect::<HashSet<_>>();
+        for tx in data.transactions.iter() {
+            // templates and their lines are reminders
+            let template = index
+                .transaction(tx.parent_id)
+                .is_some_and(|p| p.is_template != 0);
+            if tx.is_template != 0 || template || parents.contains(&tx.id) {
+                continue;
+            }
+
+            let sides = [
+                (tx.from_account_id, tx.from_amount),
+                (tx.to_account_id, tx.to_amount),
+            ];
+            if selection.before(tx.datetime) {
+                for (id, amount) in sides
+                    .into_iter()
+                    .filter(|(id, _)| *id != 0 && selected(*id))
+                {
+                    account(&mut accounts, id).financisto.opening += amount;
+                }
+                continue;
+            }
+            if !selection.contains(tx.datetime) {
+                continue;
+            }
+
+            match (tx.to_account_id != 0, selected(tx.from_account_id)) {
+                // a transfer with a side outside the selection is an income or expense of the other
+                (true, from) => match (from, selected(tx.to_account_id)) {
+                    (true, true) => {
+                        account(&mut accounts, tx.from_account_id)
+                            .financisto
+                            .transfer_out += tx.from_amount;
+                        account(&mut accounts, tx.to_account_id)
+                            .financisto
+                            .transfer_in += tx.to_amount;
+                    }
+                    (true, false) => earn(&mut accounts, tx.from_account_id, tx.from_amount),
+                    (false, true) => earn(&mut accounts, tx.to_account_id, tx.to_amount),
+                    (false, false) => {}
+                },
+                (false, true) if picked(tx.category_id) => {
+                    earn(&mut accounts, tx.from_account_id, tx.from_amount)
+                }
+                _ => {}
+            }
+        }
+
+        let mut bluecoins = BTreeMap::new();
+        for statement in statements.iter() {
+            let row = Insert::parse(statement)?;
+            *bluecoins.entry(row.table.to_owned()).or_default() += 1;
+
+            // reminders are not transactions yet
+            if row.table != "TRANSACTIONSTABLE" || row.get("reminderTransaction") == Some("1") {
+                continue;
+            }
+
+            // 6 digit in bluecoins, 2 digit in financisto
+            let amount = row.int("amount") / 10000;
+            let totals = &mut account(&mut accounts, row.int("accountID") as i32 - 5).bluecoins;
+            match row.int("transactionTypeID") as i32 {
+                TYPE_INCOME => totals.income += amount,
+                TYPE_EXPENSE => totals.expense += amount,
+                TYPE_TRANSFER if row.int("accountReference") == 2 => totals.transfer_in += amount,
+                TYPE_TRANSFER => totals.transfer_out += amount,
+                _ => totals.opening += amount,
+            }
+        }
+
+        let accounts = accounts
+            .into_values()
+            .map(|mut a| {
+                a.financisto.close();
+                a.bluecoins.close();
+                a.mismatch = a.financisto != a.bluecoins
+                    || a.stored_balance.is_some_and(|b| b != a.financisto.balance);
+                a
+            })
+            .collect();
+
+        Ok(Self {
+            financisto,
+            bluecoins,
+            accounts,
+        })
+    }
+
+    /// Accounts whose Bluecoins totals differ from Financisto, or whose Financisto totals differ
+    /// from the balance it stored.
+    pub fn mismatches(&self) -> impl Iterator<Item = &AccountSummary> {
+        self.accounts.iter().filter(|a| a.mismatch)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl Financisto {
+    /// Balance of every account the way Financisto keeps it in `total_amount`: a split counts by
+    /// its own amount and its lines only move money to the accounts they transfer to, templates
+    /// and their lines do not count.
+    pub fn balances(&self) -> BTreeMap<i32, i64> {
+        let index = self.index();
+        let mut balances = self
+            .accounts
+            .iter()
+            .map(|a| (a.id, 0))
+            .collect::<BTreeMap<_, _>>();
+        for tx in self.transactions.iter().filter(|t| t.is_template == 0) {
+            let parent = index.transaction(tx.parent_id);
+            if parent.is_some_and(|p| p.is_template != 0) {
+                continue;
+            }
+            if parent.is_none() {
+                *balances.entry(tx.from_account_id).or_default() += tx.from_amount;
+            }
+            if tx.to_account_id != 0 {
+                *balances.entry(tx.to_account_id).or_default() += tx.to_amount;
+            }
+        }
+        balances
+    }
+}
+
+/// Summary of the account, also for accounts only known from the transactions.
+fn account(accounts: &mut BTreeMap<i32, AccountSummary>, id: i32) -> &mut AccountSummary {
+    accounts
+        .entry(id)
+        .or_insert_with(|| AccountSummary::new(id, &format!("<unknown {}>", id)))
+}
+
+/// Add an income or expense, by its sign, to the Financisto totals of the account.
+fn earn(accounts: &mut BTreeMap<i32, AccountSummary>, id: i32, amount: i64) {
+    let totals = &mut account(accounts, id).financisto;
+    if amount >= 0 {
+        totals.income += amount;
+    } else {
+        totals.expense += amount;
+    }
+}
+
+/// Whether the selection keeps the entity of the ID and titles, named by either.
+fn selects(selection: &Selection, id: i32, titles: &[&str]) -> bool {
+    let named = |names: &[String]| {
+        names
+            .iter()
+            .any(|n| *n == id.to_string() || titles.contains(&n.as_str()))
+    };
+    (selection.include.is_empty() || named(&selection.include)) && !named(&selection.exclude)
+}
+
+/// The date range of a filter, in milliseconds.
+struct FilterRange {
+    from: Option<i64>,
+    until: Option<i64>,
+}
+
+impl FilterRange {
+    fn new(filter: &Filter, timezone: Timezone) -> Result<Self> {
+        let midnight = |date: Option<NaiveDate>| {
+            date.map(|date| {
+                timezone
+                    .midnight(date)
+                    .ok_or_else(|| format!("invalid date {}", date))
+            })
+            .transpose()
+        };
+        Ok(Self {
+            from: midnight(filter.from)?,
+            until: midnight(filter.to.and_then(|to| to.succ_opt()))?,
+        })
+    }
+
+    fn before(&self, millis: i64) -> bool {
+        self.from.is_some_and(|from| millis < from)
+    }
+
+    fn contains(&self, millis: i64) -> bool {
+        !self.before(millis) && self.until.is_none_or(|until| millis < until)
+    }
+}
+
+/// Cents as a decimal amount.
+fn money(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Financisto")?;
+        for (kind, count) in self.financisto.iter() {
+            writeln!(f, "  {:<20} {:>8}", kind, count)?;
+        }
+        writeln!(f, "Bluecoins")?;
+        for (table, count) in self.bluecoins.iter() {
+            writeln!(f, "  {:<20} {:>8}", table, count)?;
+        }
+
+        writeln!(
+            f,
+            "{:<3}{:<20} {:<10} {:>14} {:>14} {:>14} {:>14} {:>14} {:>14}",
+            "",
+            "account",
+            "source",
+            "opening",
+            "income",
+            "expense",
+            "transfer in",
+            "transfer out",
+            "balance"
+        )?;
+        for account in self.accounts.iter() {
+            // mismatches are marked with !!
+            let marker = if account.mismatch { "!!" } else { "" };
+            for (source, totals) in [
+                ("financisto", &account.financisto),
+                ("bluecoins", &account.bluecoins),
+            ] {
+                writeln!(
+                    f,
+                    "{:<3}{:<20} {:<10} {:>14} {:>14} {:>14} {:>14} {:>14} {:>14}",
+                    marker,
+                    account.title,
+                    source,
+                    money(totals.opening),
+                    money(totals.income),
+                    money(totals.expense),
+                    money(totals.transfer_in),
+                    money(totals.transfer_out),
+                    money(totals.balance),
+                )?;
+            }
+            if let Some(stored) = account
+                .stored_balance
+                .filter(|b| *b != account.financisto.balance)
+            {
+                writeln!(
+                    f,
+                    "{:<3}{:<20} {:<10} {:>14} {:>14} {:>14} {:>14} {:>14} {:>14}",
+                    marker,
+                    account.title,
+                    "stored",
+                    "",
+                    "",
+                    "",
+                    "",
+                    "",
+                    money(stored),
+                )?;
+            }
+        }
+
+        match self.mismatches().count() {
+            0 => write!(f, "all accounts match"),
+            count => write!(f, "{} account(s) do not match", count),
+        }
+    }
+}
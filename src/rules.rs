@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{CategoryTree, Financisto, FinancistoTransaction, Result};
+
+/// Declarative recategorization rules, applied to every transaction before SQL is generated.
+///
+/// Rules are matched against values that do not change between runs (notes, payee, account and
+/// category titles, amounts), so one rules file stays valid for every migration of the same book.
+/// Every matching rule is applied in file order: later rules override the category and item of
+/// earlier ones, labels are accumulated.
+///
+/// ```toml
+/// [[rule]]
+/// match = { note = "lunch", type = "expense", max_amount = 500 }
+/// set = { category = "Food:Lunch", labels = ["work"] }
+///
+/// # move ticket numbers out of the item name
+/// [[rule]]
+/// match = { account = "Lottery", note_pattern = '^[+-]?\d+$' }
+/// set = { item = "Lottery ticket", notes = "No. $0" }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rules {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    #[serde(default, rename = "match")]
+    pub condition: RuleMatch,
+    #[serde(default)]
+    pub set: RuleAction,
+}
+
+/// Conditions of a rule, all given conditions must hold.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleMatch {
+    /// Note contains this text, case-insensitive.
+    pub note: Option<String>,
+    /// Note matches this regular expression, its captures can be used in `set.notes`.
+    pub note_pattern: Option<String>,
+    /// Payee title.
+    pub payee: Option<String>,
+    /// Title of the source or target account.
+    pub account: Option<String>,
+    /// Category title, or its path such as `Parent:Child`.
+    pub category: Option<String>,
+    /// Lower bound of the absolute amount, in currency units.
+    pub min_amount: Option<f64>,
+    /// Upper bound of the absolute amount, in currency units.
+    pub max_amount: Option<f64>,
+    pub r#type: Option<TransactionKind>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleAction {
+    /// Category title, or its path such as `Parent:Child`.
+    pub category: Option<String>,
+    /// Bluecoins item name.
+    pub item: Option<String>,
+    /// Bluecoins notes, `$1` or `${name}` are replaced by the captures of `match.note_pattern`.
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionKind {
+    Expense,
+    Income,
+    Transfer,
+}
+
+impl TransactionKind {
+    pub fn of(tx: &FinancistoTransaction) -> Self {
+        if tx.to_account_id != 0 {
+            TransactionKind::Transfer
+        } else if tx.from_amount >= 0 {
+            TransactionKind::Income
+        } else {
+            TransactionKind::Expense
+        }
+    }
+}
+
+impl Rules {
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// Load a rules file, JSON if the extension is `.json`, TOML otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let text = fs::read_to_string(&path)?;
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&text),
+            _ => Self::from_toml(&text),
+        }
+    }
+
+    /// Resolve the titles used by the rules against the Financisto data.
+    pub fn resolve(&self, data: &Financisto) -> Result<RuleSet<'_>> {
+        let accounts = titles(data.accounts.iter().map(|a| (a.title.to_owned(), a.id)));
+        let payees = titles(data.payees.iter().map(|p| (p.title.to_owned(), p.id)));
+        let categories = category_titles(&CategoryTree::new(&data.categories));
+
+        let mut rules = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let find = |kind: &str, lookup: &HashMap<String, Vec<i32>>, title: &Option<String>| {
+                lookup_title(kind, lookup, title)
+                    .map_err(|message| format!("rule {}: {}", index + 1, message))
+            };
+
+            let note_pattern = match &rule.condition.note_pattern {
+                Some(pattern) => Some(Regex::new(pattern).map_err(|e| {
+                    format!(
+                        "rule {}: invalid note_pattern '{}': {}",
+                        index + 1,
+                        pattern,
+                        e
+                    )
+                })?),
+                None => None,
+            };
+
+            rules.push(ResolvedRule {
+                rule,
+                note: rule.condition.note.as_ref().map(|n| n.to_lowercase()),
+                note_pattern,
+                payee_id: find("payee", &payees, &rule.condition.payee)?,
+                account_id: find("account", &accounts, &rule.condition.account)?,
+                category_id: find("category", &categories, &rule.condition.category)?,
+                set_category_id: find("category", &categories, &rule.set.category)?,
+            });
+        }
+
+        Ok(RuleSet { rules })
+    }
+}
+
+pub(crate) fn titles<I: Iterator<Item = (String, i32)>>(iter: I) -> HashMap<String, Vec<i32>> {
+    let mut map: HashMap<String, Vec<i32>> = HashMap::new();
+    for (title, id) in iter {
+        let ids = map.entry(title).or_default();
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    map
+}
+
+/// Categories by title and by "Parent:Child" path.
+pub(crate) fn category_titles(tree: &CategoryTree) -> HashMap<String, Vec<i32>> {
+    titles(tree.iter().flat_map(|c| {
+        let path = tree
+            .path(c.id)
+            .iter()
+            .map(|p| p.title.as_str())
+            .collect::<Vec<_>>()
+            .join(":");
+        [(c.title.to_owned(), c.id), (path, c.id)]
+    }))
+}
+
+pub(crate) fn lookup_title(
+    kind: &str,
+    lookup: &HashMap<String, Vec<i32>>,
+    title: &Option<String>,
+) -> std::result::Result<Option<i32>, String> {
+    match title {
+        None => Ok(None),
+        Some(title) => match lookup.get(title).map(|ids| ids.as_slice()) {
+            Some([id]) => Ok(Some(*id)),
+            Some(ids) if ids.len() > 1 => Err(format!(
+                "{} '{}' is ambiguous, matches IDs {:?}",
+                kind, title, ids
+            )),
+            _ => Err(format!("unknown {} '{}'", kind, title)),
+        },
+    }
+}
+
+/// Rules with their titles resolved to Financisto IDs.
+pub struct RuleSet<'a> {
+    rules: Vec<ResolvedRule<'a>>,
+}
+
+struct ResolvedRule<'a> {
+    rule: &'a Rule,
+    note: Option<String>,
+    note_pattern: Option<Regex>,
+    payee_id: Option<i32>,
+    account_id: Option<i32>,
+    category_id: Option<i32>,
+    set_category_id: Option<i32>,
+}
+
+impl ResolvedRule<'_> {
+    fn expand_notes(&self, tx: &FinancistoTransaction, template: &str) -> String {
+        let captures = self
+            .note_pattern
+            .as_ref()
+            .zip(tx.note.as_ref())
+            .and_then(|(pattern, note)| pattern.captures(note));
+
+        match captures {
+            Some(captures) => {
+                let mut notes = String::new();
+                captures.expand(template, &mut notes);
+                notes
+            }
+            None => template.to_owned(),
+        }
+    }
+
+    fn matches(&self, tx: &FinancistoTransaction) -> bool {
+        let condition = &self.rule.condition;
+        let amount = (tx.from_amount as f64 / 100.0).abs();
+
+        self.note.as_ref().is_none_or(|note| {
+            tx.note
+                .as_ref()
+                .is_some_and(|n| n.to_lowercase().contains(note))
+        }) && self
+            .note_pattern
+            .as_ref()
+            .is_none_or(|pattern| tx.note.as_ref().is_some_and(|n| pattern.is_match(n)))
+            && self.payee_id.is_none_or(|id| tx.payee_id == id)
+            && self
+                .account_id
+                .is_none_or(|id| tx.from_account_id == id || tx.to_account_id == id)
+            && self.category_id.is_none_or(|id| tx.category_id == id)
+            && condition.min_amount.is_none_or(|min| amount >= min)
+            && condition.max_amount.is_none_or(|max| amount <= max)
+            && condition
+                .r#type
+                .is_none_or(|kind| TransactionKind::of(tx) == kind)
+    }
+}
+
+/// Changes the matching rules make to a transaction.
+#[derive(Debug, Default, PartialEq)]
+pub struct RuleOutcome {
+    /// Financisto category ID replacing the transaction's own.
+    pub category_id: Option<i32>,
+    pub item: Option<String>,
+    pub notes: Option<String>,
+    pub labels: Vec<String>,
+}
+
+impl RuleSet<'_> {
+    pub fn apply(&self, tx: &FinancistoTransaction) -> RuleOutcome {
+        let mut outcome = RuleOutcome::default();
+
+        for rule in self.rules.iter().filter(|r| r.matches(tx)) {
+            if rule.set_category_id.is_some() {
+                outcome.category_id = rule.set_category_id;
+            }
+            if rule.rule.set.item.is_some() {
+                outcome.item = rule.rule.set.item.to_owned();
+            }
+            if let Some(template) = &rule.rule.set.notes {
+                outcome.notes = Some(rule.expand_notes(tx, template));
+            }
+            for label in rule.rule.set.labels.iter() {
+                if !outcome.labels.contains(label) {
+                    outcome.labels.push(label.to_owned());
+                }
+            }
+        }
+
+        outcome
+    }
+}
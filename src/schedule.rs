@@ -0,0 +1,241 @@
+use chrono::{Datelike, Days, Months, NaiveDateTime, Weekday};
+
+use crate::bluecoins::*;
+use crate::{FinancistoTransaction, Result, Timezone};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Once,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Until {
+    Indefinitely,
+    Times(i32),
+    Date(NaiveDateTime),
+}
+
+/// Recurrence of a Financisto scheduled transaction.
+///
+/// Financisto stores it as `start~pattern~period`, for example
+/// `2022-12-01T09:00:00~MONTHLY:interval@1#~EXACTLY_TIMES:12`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    pub start: NaiveDateTime,
+    pub frequency: Frequency,
+    pub interval: i32,
+    pub until: Until,
+}
+
+impl Recurrence {
+    /// Parse a Financisto recurrence, its end date is taken in the timezone.
+    pub fn parse(text: &str, timezone: Timezone) -> Result<Self> {
+        let parts = text.split('~').collect::<Vec<_>>();
+        if parts.len() != 3 {
+            return Err(format!("malformed recurrence '{}'", text).into());
+        }
+
+        let mut start = NaiveDateTime::parse_from_str(parts[0], "%Y-%m-%dT%H:%M:%S")?;
+        let (frequency, params) = parts[1].split_once(':').unwrap_or((parts[1], ""));
+        // parameters end with a `#` terminator
+        let params = params.trim_end_matches('#');
+        let (frequency, interval) = match frequency {
+            "NO_RECUR" => (Frequency::Once, 1),
+            "DAILY" => (Frequency::Daily, param(params, '#', '@', "interval")?),
+            "WEEKLY" => {
+                // Bluecoins repeats on the weekday of the start, so only one day fits
+                if let Some(day) = weekday(params)? {
+                    let ahead = (day.num_days_from_monday() + 7
+                        - start.weekday().num_days_from_monday())
+                        % 7;
+                    start = start
+                        .checked_add_days(Days::new(ahead.into()))
+                        .ok_or_else(|| format!("invalid start '{}'", parts[0]))?;
+                }
+                (Frequency::Weekly, param(params, '#', '@', "interval")?)
+            }
+            "MONTHLY" => (Frequency::Monthly, param(params, '#', '@', "interval")?),
+            // free-form RRULE, e.g. FREQ=YEARLY;INTERVAL=1
+            "GEEKY" => {
+                let frequency = match params
+                    .split(';')
+                    .find_map(|p| p.strip_prefix("FREQ="))
+                    .unwrap_or_default()
+                {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    other => return Err(format!("unsupported RRULE frequency '{}'", other).into()),
+                };
+                (frequency, param(params, ';', '=', "INTERVAL")?)
+            }
+            other => return Err(format!("unsupported recurrence '{}'", other).into()),
+        };
+
+        let (until, value) = parts[2].split_once(':').unwrap_or((parts[2], ""));
+        let until = match until {
+            "INDEFINETELY" | "INDEFINITELY" => Until::Indefinitely,
+            "EXACTLY_TIMES" => Until::Times(value.parse()?),
+            "STOPS_ON_DATE" => Until::Date(
+                timezone
+                    .naive(value.parse()?)
+                    .ok_or_else(|| format!("invalid end date '{}'", value))?,
+            ),
+            other => return Err(format!("unsupported recurrence period '{}'", other).into()),
+        };
+
+        Ok(Self {
+            start,
+            frequency,
+            interval,
+            until,
+        })
+    }
+
+    /// Recurrence of a template or scheduled transaction from its first occurrence after
+    /// `last_recurrence`, `None` for templates that are not scheduled or whose recurrence is over.
+    pub fn of(tx: &FinancistoTransaction, timezone: Timezone) -> Result<Option<Self>> {
+        let recurrence = match tx.recurrence.as_deref() {
+            Some(recurrence) if !recurrence.is_empty() => Self::parse(recurrence, timezone)?,
+            // a scheduled transaction without recurrence fires once
+            _ if is_scheduled(tx) => Self {
+                start: timezone
+                    .naive(tx.datetime)
+                    .ok_or_else(|| format!("invalid date {}", tx.datetime))?,
+                frequency: Frequency::Once,
+                interval: 1,
+                until: Until::Times(1),
+            },
+            _ => return Ok(None),
+        };
+        if tx.last_recurrence <= 0 {
+            return Ok(Some(recurrence));
+        }
+
+        let last = timezone
+            .naive(tx.last_recurrence)
+            .ok_or_else(|| format!("invalid last recurrence {}", tx.last_recurrence))?;
+        Ok(recurrence.after(last))
+    }
+
+    /// The recurrence from its first occurrence after the time, `None` if it ends before.
+    pub fn after(&self, time: NaiveDateTime) -> Option<Self> {
+        let mut count = 0;
+        loop {
+            let start = self.occurrence(count)?;
+            match self.until {
+                Until::Times(times) if count >= times => return None,
+                Until::Date(date) if start > date => return None,
+                _ => {}
+            }
+            if start > time {
+                return Some(Self {
+                    start,
+                    until: match self.until {
+                        Until::Times(times) => Until::Times(times - count),
+                        until => until,
+                    },
+                    ..self.clone()
+                });
+            }
+            count += 1;
+        }
+    }
+
+    /// Time of an occurrence counted from 0, `None` past the range of dates.
+    fn occurrence(&self, count: i32) -> Option<NaiveDateTime> {
+        let steps = count.checked_mul(self.interval)?;
+        match self.frequency {
+            Frequency::Once if count > 0 => None,
+            Frequency::Once => Some(self.start),
+            Frequency::Daily => self.start.checked_add_days(Days::new(steps as u64)),
+            Frequency::Weekly => self
+                .start
+                .checked_add_days(Days::new(steps.checked_mul(7)? as u64)),
+            Frequency::Monthly => self.start.checked_add_months(Months::new(steps as u32)),
+            Frequency::Yearly => self
+                .start
+                .checked_add_months(Months::new(steps.checked_mul(12)? as u32)),
+        }
+    }
+
+    /// Reminder columns of the Bluecoins rows of a reminder group.
+    pub fn reminder(&self, group_id: i64) -> BluecoinsReminder {
+        let (ending_type, end_date, after_occurrences) = match self.until {
+            Until::Indefinitely => (REMINDER_ENDS_NEVER, None, None),
+            Until::Times(times) => (REMINDER_ENDS_AFTER, None, Some(times)),
+            Until::Date(date) => (
+                REMINDER_ENDS_ON_DATE,
+                Some(date.format("%Y-%m-%d %H:%M:%S").to_string()),
+                None,
+            ),
+        };
+
+        BluecoinsReminder {
+            group_id,
+            frequency: match self.frequency {
+                Frequency::Once => REMINDER_ONCE,
+                Frequency::Daily => REMINDER_DAILY,
+                Frequency::Weekly => REMINDER_WEEKLY,
+                Frequency::Monthly => REMINDER_MONTHLY,
+                Frequency::Yearly => REMINDER_YEARLY,
+            },
+            repeat_every: self.interval,
+            ending_type,
+            start_date: self.start.format("%Y-%m-%d %H:%M:%S").to_string(),
+            end_date,
+            after_occurrences,
+        }
+    }
+}
+
+/// A scheduled transaction, or a template with a recurrence.
+pub(crate) fn is_scheduled(tx: &FinancistoTransaction) -> bool {
+    tx.is_template == 2 || tx.recurrence.as_deref().is_some_and(|r| !r.is_empty())
+}
+
+/// The one day of a weekly pattern like `days@MON`, `None` if it has no days.
+fn weekday(params: &str) -> Result<Option<Weekday>> {
+    let Some((_, days)) = params
+        .split('#')
+        .filter_map(|p| p.split_once('@'))
+        .find(|(key, _)| *key == "days")
+    else {
+        return Ok(None);
+    };
+    let day = match days {
+        "" => return Ok(None),
+        "SUN" => Weekday::Sun,
+        "MON" => Weekday::Mon,
+        "TUE" => Weekday::Tue,
+        "WED" => Weekday::Wed,
+        "THR" | "THU" => Weekday::Thu,
+        "FRI" => Weekday::Fri,
+        "SAT" => Weekday::Sat,
+        _ if days.contains(',') => {
+            return Err(format!("unsupported weekly recurrence on several days '{}'", days).into())
+        }
+        _ => return Err(format!("invalid day '{}'", days).into()),
+    };
+    Ok(Some(day))
+}
+
+/// Positive integer parameter of a recurrence pattern, 1 if absent.
+fn param(params: &str, separator: char, assign: char, name: &str) -> Result<i32> {
+    match params
+        .split(separator)
+        .filter_map(|p| p.split_once(assign))
+        .find(|(key, _)| *key == name)
+    {
+        Some((_, value)) => match value.parse()? {
+            value if value > 0 => Ok(value),
+            _ => Err(format!("invalid {} '{}'", name, value).into()),
+        },
+        None => Ok(1),
+    }
+}
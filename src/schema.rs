@@ -0,0 +1,164 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{RawEntities, Result};
+
+/// Fields of each entity kind, as documented in `data/fields.json`.
+const KNOWN_SCHEMA: &str = include_str!("../data/fields.json");
+
+/// Field names of each entity kind, in the format of `data/fields.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Schema {
+    pub entities: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A difference between the schema of a backup and a stored one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    NewEntity {
+        entity: String,
+    },
+    /// Entity kind not in the backup, which may also just have none of them.
+    MissingEntity {
+        entity: String,
+    },
+    NewField {
+        entity: String,
+        field: String,
+    },
+    /// Field not in the backup, which may also just be empty in every entity.
+    MissingField {
+        entity: String,
+        field: String,
+    },
+    /// A missing field with a new one of a similar name.
+    RenamedField {
+        entity: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NewEntity { entity } => write!(f, "new entity {}", entity),
+            Self::MissingEntity { entity } => write!(f, "missing entity {}", entity),
+            Self::NewField { entity, field } => write!(f, "new field {}.{}", entity, field),
+            Self::MissingField { entity, field } => {
+                write!(f, "missing field {}.{}", entity, field)
+            }
+            Self::RenamedField { entity, from, to } => {
+                write!(f, "field {}.{} renamed to {}", entity, from, to)
+            }
+        }
+    }
+}
+
+impl Schema {
+    /// Schema of the entities read from a backup or database.
+    pub fn of(entities: &RawEntities) -> Self {
+        let entities = entities
+            .types()
+            .map(|entity| {
+                let fields = entities.fields(entity);
+                (
+                    entity.to_owned(),
+                    fields.into_iter().map(|f| f.to_owned()).collect(),
+                )
+            })
+            .collect();
+
+        Self { entities }
+    }
+
+    /// The schema documented in `data/fields.json`.
+    pub fn known() -> Self {
+        Self::from_json(KNOWN_SCHEMA).expect("data/fields.json is a valid schema")
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// What changed from the `stored` schema to this one.
+    pub fn diff(&self, stored: &Schema) -> Vec<SchemaChange> {
+        let mut changes = Vec::new();
+
+        for entity in stored.entities.keys() {
+            if !self.entities.contains_key(entity) {
+                changes.push(SchemaChange::MissingEntity {
+                    entity: entity.to_owned(),
+                });
+            }
+        }
+
+        for (entity, fields) in self.entities.iter() {
+            let Some(known) = stored.entities.get(entity) else {
+                changes.push(SchemaChange::NewEntity {
+                    entity: entity.to_owned(),
+                });
+                continue;
+            };
+
+            let mut missing = known.difference(fields).collect::<Vec<_>>();
+            for field in fields.difference(known) {
+                // pair the new field with the most similar missing one
+                let renamed = missing
+                    .iter()
+                    .enumerate()
+                    .map(|(index, from)| (index, similarity(from, field)))
+                    .filter(|(_, score)| *score >= 0.5)
+                    .max_by(|a, b| a.1.total_cmp(&b.1));
+                match renamed {
+                    Some((index, _)) => changes.push(SchemaChange::RenamedField {
+                        entity: entity.to_owned(),
+                        from: missing.remove(index).to_owned(),
+                        to: field.to_owned(),
+                    }),
+                    None => changes.push(SchemaChange::NewField {
+                        entity: entity.to_owned(),
+                        field: field.to_owned(),
+                    }),
+                }
+            }
+            for field in missing {
+                changes.push(SchemaChange::MissingField {
+                    entity: entity.to_owned(),
+                    field: field.to_owned(),
+                });
+            }
+        }
+
+        changes
+    }
+}
+
+/// 1 for equal names down to 0 for names without anything in common, by edit distance.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::Result;
+
+/// An `INSERT INTO "TABLE" ("column", ...) VALUES ('value', NULL, ...);` statement, as written by
+/// the migrators, or a row read back from a Bluecoins database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Insert {
+    pub table: String,
+    pub columns: Vec<String>,
+    /// `None` for NULL.
+    pub values: Vec<Option<String>>,
+}
+
+impl Insert {
+    pub fn parse(statement: &str) -> Result<Self> {
+        let malformed = || format!("malformed statement: {}", statement);
+
+        let rest = statement
+            .trim()
+            .strip_prefix("INSERT INTO ")
+            .ok_or_else(malformed)?;
+        let (table, rest) = identifier(rest).ok_or_else(malformed)?;
+        let rest = rest.trim_start().strip_prefix('(').ok_or_else(malformed)?;
+
+        let mut columns = Vec::new();
+        let mut rest = rest;
+        loop {
+            let (column, tail) = identifier(rest.trim_start()).ok_or_else(malformed)?;
+            columns.push(column);
+            let tail = tail.trim_start();
+            if let Some(tail) = tail.strip_prefix(',') {
+                rest = tail;
+            } else {
+                rest = tail.strip_prefix(')').ok_or_else(malformed)?;
+                break;
+            }
+        }
+
+        let mut rest = rest
+            .trim_start()
+            .strip_prefix("VALUES")
+            .and_then(|r| r.trim_start().strip_prefix('('))
+            .ok_or_else(malformed)?;
+        let mut values = Vec::new();
+        loop {
+            let tail = rest.trim_start();
+            let (value, tail) = if let Some(tail) = tail.strip_prefix("NULL") {
+                (None, tail)
+            } else {
+                let (value, tail) = quoted(tail, '\'').ok_or_else(malformed)?;
+                (Some(value), tail)
+            };
+            values.push(value);
+            let tail = tail.trim_start();
+            if let Some(tail) = tail.strip_prefix(',') {
+                rest = tail;
+            } else {
+                tail.strip_prefix(')').ok_or_else(malformed)?;
+                break;
+            }
+        }
+
+        if columns.len() != values.len() {
+            return Err(format!(
+                "{} columns but {} values: {}",
+                columns.len(),
+                values.len(),
+                statement
+            )
+            .into());
+        }
+
+        Ok(Self {
+            table,
+            columns,
+            values,
+        })
+    }
+
+    /// Value of the column, `None` if the column is missing or NULL.
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .position(|c| c == column)
+            .and_then(|index| self.values[index].as_deref())
+    }
+
+    /// Integer value of the column, 0 if missing, NULL or not a number.
+    pub fn int(&self, column: &str) -> i64 {
+        self.get(column)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Columns by name.
+    pub fn to_map(&self) -> HashMap<&str, Option<&str>> {
+        self.columns
+            .iter()
+            .map(|c| c.as_str())
+            .zip(self.values.iter().map(|v| v.as_deref()))
+            .collect()
+    }
+}
+
+/// A `"quoted"` identifier and what follows it.
+fn identifier(text: &str) -> Option<(String, &str)> {
+    quoted(text, '"')
+}
+
+/// A string in `quote`s, with doubled quotes unescaped, and what follows it.
+fn quoted(text: &str, quote: char) -> Option<(String, &str)> {
+    let mut chars = text.char_indices();
+    if chars.next()?.1 != quote {
+        return None;
+    }
+
+    let mut value = String::new();
+    let mut escaped = false;
+    for (index, c) in chars {
+        if escaped {
+            escaped = false;
+            if c == quote {
+                value.push(c);
+                continue;
+            }
+            return Some((value, &text[index..]));
+        }
+        if c == quote {
+            escaped = true;
+        } else {
+            value.push(c);
+        }
+    }
+
+    // closing quote at the very end
+    escaped.then_some((value, ""))
+}
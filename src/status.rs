@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+/// Bluecoins `status` of a transaction that is neither cleared nor reconciled.
+pub const STATUS_NONE: i32 = 0;
+/// Bluecoins `status` of a cleared transaction.
+pub const STATUS_CLEARED: i32 = 1;
+/// Bluecoins `status` of a reconciled transaction.
+pub const STATUS_RECONCILED: i32 = 2;
+
+/// Maps Financisto transaction status codes onto Bluecoins' `status` column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusMap {
+    pub codes: HashMap<String, i32>,
+    /// Status of codes missing from `codes`.
+    pub unknown: i32,
+}
+
+impl Default for StatusMap {
+    fn default() -> Self {
+        let codes = [
+            ("RC", STATUS_RECONCILED), // reconciled
+            ("CL", STATUS_CLEARED),    // cleared
+            ("PN", STATUS_NONE),       // pending
+            ("UR", STATUS_NONE),       // unreconciled
+            ("RS", STATUS_NONE),       // restored
+        ]
+        .into_iter()
+        .map(|(code, status)| (code.to_owned(), status))
+        .collect();
+
+        Self {
+            codes,
+            unknown: STATUS_NONE,
+        }
+    }
+}
+
+impl StatusMap {
+    /// Bluecoins status of a Financisto status code, transactions without one are not cleared.
+    pub fn resolve(&self, code: Option<&str>) -> i32 {
+        match code {
+            None | Some("") => STATUS_NONE,
+            Some(code) => self.codes.get(code).copied().unwrap_or(self.unknown),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+
+use crate::Result;
+
+/// Timezone the Financisto timestamps are written in Bluecoins with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timezone {
+    /// Timezone of the machine running the conversion.
+    #[default]
+    Local,
+    /// IANA timezone, e.g. `Asia/Taipei`.
+    Named(Tz),
+    /// Fixed offset from UTC, e.g. `+08:00`.
+    Fixed(FixedOffset),
+}
+
+impl FromStr for Timezone {
+    type Err = Box<dyn std::error::Error + Send + Sync>;
+
+    /// `local`, an IANA name, `UTC` or an offset like `+08:00` or `-0530`.
+    fn from_str(text: &str) -> Result<Self> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+        if let Ok(tz) = text.parse::<Tz>() {
+            return Ok(Self::Named(tz));
+        }
+
+        let sign = match text.chars().next() {
+            Some('+') => 1,
+            Some('-') => -1,
+            _ => return Err(format!("unknown timezone '{}'", text).into()),
+        };
+        let digits = text[1..].replace(':', "");
+        let (hours, minutes) = match digits.len() {
+            2 => (digits.parse::<i32>()?, 0),
+            4 => (digits[..2].parse::<i32>()?, digits[2..].parse::<i32>()?),
+            _ => return Err(format!("unknown timezone '{}'", text).into()),
+        };
+
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+            .map(Self::Fixed)
+            .ok_or_else(|| format!("timezone offset '{}' out of range", text).into())
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Named(tz) => write!(f, "{}", tz.name()),
+            Self::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+impl Timezone {
+    /// Wall clock time of a timestamp in milliseconds.
+    pub fn naive(&self, millis: i64) -> Option<NaiveDateTime> {
+        match self {
+            Self::Local => chrono::Local
+                .timestamp_millis_opt(millis)
+                .single()
+                .map(|t| t.naive_local()),
+            Self::Named(tz) => tz
+                .timestamp_millis_opt(millis)
+                .single()
+                .map(|t| t.naive_local()),
+            Self::Fixed(offset) => offset
+                .timestamp_millis_opt(millis)
+                .single()
+                .map(|t| t.naive_local()),
+        }
+    }
+
+    /// Timestamp as Bluecoins writes dates, `2022-12-01 09:00:00`.
+    pub fn format(&self, millis: i64) -> Result<String> {
+        self.naive(millis)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .ok_or_else(|| format!("invalid timestamp {}", millis).into())
+    }
+
+    /// Timestamp in milliseconds of a wall clock time, the earlier one if it is ambiguous.
+    pub fn millis(&self, time: &NaiveDateTime) -> Option<i64> {
+        match self {
+            Self::Local => chrono::Local
+                .from_local_datetime(time)
+                .earliest()
+                .map(|t| t.timestamp_millis()),
+            Self::Named(tz) => tz
+                .from_local_datetime(time)
+                .earliest()
+                .map(|t| t.timestamp_millis()),
+            Self::Fixed(offset) => offset
+                .from_local_datetime(time)
+                .earliest()
+                .map(|t| t.timestamp_millis()),
+        }
+    }
+
+    /// Timestamp in milliseconds of the first moment of the day.
+    pub fn midnight(&self, date: NaiveDate) -> Option<i64> {
+        self.millis(&date.and_hms_opt(0, 0, 0)?)
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use rusqlite::types::ValueRef;
+
+use crate::{Insert, Result, TYPE_TRANSFER};
+
+/// Categories every Bluecoins database already has: new account (2) and transfer (3).
+const BUILTIN_CATEGORIES: [i64; 2] = [2, 3];
+/// Items every Bluecoins database already has: Unnamed Expense (2) and Unnamed Income (3).
+const BUILTIN_ITEMS: [i64; 2] = [2, 3];
+
+/// Tables read by [`verify_sqlite`].
+const TABLES: [&str; 4] = [
+    "ACCOUNTSTABLE",
+    "CHILDCATEGORYTABLE",
+    "ITEMTABLE",
+    "TRANSACTIONSTABLE",
+];
+
+/// A broken invariant of Bluecoins data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    DuplicateTransaction {
+        transaction_id: i64,
+    },
+    /// Transfer group without exactly two legs.
+    TransferLegs {
+        group_id: i64,
+        legs: usize,
+    },
+    /// Transfer leg whose `uidPairID` is not the other leg of its group.
+    TransferPair {
+        transaction_id: i64,
+        uid_pair_id: i64,
+    },
+    /// Split line whose `newSplitTransactionID` is another transaction, or whose group spans
+    /// several accounts or dates.
+    SplitGroup {
+        transaction_id: i64,
+        split_id: i64,
+    },
+    UnknownAccount {
+        transaction_id: i64,
+        account_id: i64,
+    },
+    UnknownCategory {
+        transaction_id: i64,
+        category_id: i64,
+    },
+    UnknownItem {
+        transaction_id: i64,
+        item_id: i64,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateTransaction { transaction_id } => {
+                write!(
+                    f,
+                    "transaction ID {} is used more than once",
+                    transaction_id
+                )
+            }
+            Self::TransferLegs { group_id, legs } => {
+                write!(f, "transfer group {} has {} leg(s)", group_id, legs)
+            }
+            Self::TransferPair {
+                transaction_id,
+                uid_pair_id,
+            } => write!(
+                f,
+                "transfer leg {} pairs with {} outside its group",
+                transaction_id, uid_pair_id
+            ),
+            Self::SplitGroup {
+                transaction_id,
+                split_id,
+            } => write!(
+                f,
+                "split line {} does not belong to a valid split {}",
+                transaction_id, split_id
+            ),
+            Self::UnknownAccount {
+                transaction_id,
+                account_id,
+            } => write!(
+                f,
+                "transaction {} refers to missing account {}",
+                transaction_id, account_id
+            ),
+            Self::UnknownCategory {
+                transaction_id,
+                category_id,
+            } => write!(
+                f,
+                "transaction {} refers to missing category {}",
+                transaction_id, category_id
+            ),
+            Self::UnknownItem {
+                transaction_id,
+                item_id,
+            } => write!(
+                f,
+                "transaction {} refers to missing item {}",
+                transaction_id, item_id
+            ),
+        }
+    }
+}
+
+/// Check generated rows: unique transaction IDs, paired transfer legs, consistent splits, and
+/// existing accounts, categories and items.
+pub fn verify(rows: &[Insert]) -> Vec<Violation> {
+    let ids = |table: &str, column: &str| {
+        rows.iter()
+            .filter(|r| r.table == table)
+            .map(|r| r.int(column))
+            .collect::<HashSet<_>>()
+    };
+    let accounts = ids("ACCOUNTSTABLE", "accountsTableID");
+    let mut categories = ids("CHILDCATEGORYTABLE", "categoryTableID");
+    categories.extend(BUILTIN_CATEGORIES);
+    let mut items = ids("ITEMTABLE", "itemTableID");
+    items.extend(BUILTIN_ITEMS);
+
+    let transactions = rows
+        .iter()
+        .filter(|r| r.table == "TRANSACTIONSTABLE")
+        .collect::<Vec<_>>();
+    let mut violations = Vec::new();
+
+    let mut seen = HashSet::new();
+    for row in transactions.iter() {
+        let id = row.int("transactionsTableID");
+        if !seen.insert(id) {
+            violations.push(Violation::DuplicateTransaction { transaction_id: id });
+        }
+
+        if !accounts.contains(&row.int("accountID")) {
+            violations.push(Violation::UnknownAccount {
+                transaction_id: id,
+                account_id: row.int("accountID"),
+            });
+        }
+        if !categories.contains(&row.int("categoryID")) {
+            violations.push(Violation::UnknownCategory {
+                transaction_id: id,
+                category_id: row.int("categoryID"),
+            });
+        }
+        if !items.contains(&row.int("itemID")) {
+            violations.push(Violation::UnknownItem {
+                transaction_id: id,
+                item_id: row.int("itemID"),
+            });
+        }
+    }
+
+    // transfers, by group
+    let mut groups: HashMap<i64, Vec<&Insert>> = HashMap::new();
+    for row in transactions
+        .iter()
+        .filter(|r| r.int("transactionTypeID") == TYPE_TRANSFER as i64)
+    {
+        groups
+            .entry(row.int("transferGroupID"))
+            .or_default()
+            .push(row);
+    }
+    let mut group_ids = groups.keys().copied().collect::<Vec<_>>();
+    group_ids.sort();
+    for group_id in group_ids {
+        match groups[&group_id].as_slice() {
+            [a, b] => {
+                for (leg, other) in [(a, b), (b, a)] {
+                    if leg.int("uidPairID") != other.int("transactionsTableID") {
+                        violations.push(Violation::TransferPair {
+                            transaction_id: leg.int("transactionsTableID"),
+                            uid_pair_id: leg.int("uidPairID"),
+                        });
+                    }
+                }
+            }
+            legs => violations.push(Violation::TransferLegs {
+                group_id,
+                legs: legs.len(),
+            }),
+        }
+    }
+
+    // split lines, by split
+    let split_of = transactions
+        .iter()
+        .map(|r| (r.int("transactionsTableID"), r.int("newSplitTransactionID")))
+        .collect::<HashMap<_, _>>();
+    let mut splits: HashMap<i64, Vec<&Insert>> = HashMap::new();
+    for row in transactions
+        .iter()
+        .filter(|r| r.int("newSplitTransactionID") != 0)
+    {
+        splits
+            .entry(row.int("newSplitTransactionID"))
+            .or_default()
+            .push(row);
+    }
+    for row in transactions.iter() {
+        let split_id = row.int("newSplitTransactionID");
+        if split_id == 0 {
+            continue;
+        }
+
+        let lines = &splits[&split_id];
+        let taken = split_of.get(&split_id).is_some_and(|s| *s != split_id);
+        let scattered = lines.iter().any(|l| {
+            l.int("accountID") != row.int("accountID") || l.get("date") != row.get("date")
+        });
+        if taken || scattered {
+            violations.push(Violation::SplitGroup {
+                transaction_id: row.int("transactionsTableID"),
+                split_id,
+            });
+        }
+    }
+
+    violations
+}
+
+/// Check generated SQL statements, see [`verify`].
+pub fn verify_sql(statements: &[String]) -> Result<Vec<Violation>> {
+    let rows = statements
+        .iter()
+        .map(|s| Insert::parse(s))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(verify(&rows))
+}
+
+/// Check a Bluecoins SQLite database, see [`verify`].
+pub fn verify_sqlite<P: AsRef<Path>>(path: P) -> Result<Vec<Violation>> {
+    let connection =
+        rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut rows = Vec::new();
+
+    for table in TABLES {
+        let mut statement = connection.prepare(&format!("SELECT * FROM \"{}\"", table))?;
+        let columns = statement
+            .column_names()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        let mut result = statement.query([])?;
+        while let Some(row) = result.next()? {
+            let mut values = Vec::new();
+            for index in 0..columns.len() {
+                values.push(match row.get_ref(index)? {
+                    ValueRef::Null | ValueRef::Blob(_) => None,
+                    ValueRef::Integer(value) => Some(value.to_string()),
+                    ValueRef::Real(value) => Some(value.to_string()),
+                    ValueRef::Text(value) => Some(String::from_utf8_lossy(value).into_owned()),
+                });
+            }
+            rows.push(Insert {
+                table: table.to_owned(),
+                columns: columns.clone(),
+                values,
+            });
+        }
+    }
+
+    Ok(verify(&rows))
+}
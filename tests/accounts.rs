@@ -0,0 +1,57 @@
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+#[test]
+fn credit_card_billing_cycle() -> Result<()> {
+    let mut card = account(3, "Visa", "CREDIT_CARD");
+    card.total_limit = 5000000;
+    card.closing_day = 25;
+    card.payment_day = 10;
+    let mut data = financisto(vec![]);
+    data.accounts.push(card);
+
+    let statements = migrate_accounts(&data, &MigrateOptions::default())?;
+    let accounts = statements
+        .iter()
+        .filter(|s| s.starts_with("INSERT INTO \"ACCOUNTSTABLE\""))
+        .collect::<Vec<_>>();
+
+    // creditLimit, cutOffDa, creditCardDueDate
+    assert_eq!(value_of(accounts[0], 7), "0");
+    assert_eq!(value_of(accounts[2], 2), "8");
+    assert_eq!(value_of(accounts[2], 7), "50000000000");
+    assert_eq!(value_of(accounts[2], 8), "25");
+    assert_eq!(value_of(accounts[2], 9), "10");
+
+    Ok(())
+}
+
+#[test]
+fn unknown_currency_does_not_panic() -> Result<()> {
+    let mut euro = account(3, "Euro", "CASH");
+    euro.currency_id = 9;
+    let mut data = financisto(vec![]);
+    data.accounts.push(euro);
+
+    let statements = migrate_accounts(&data, &MigrateOptions::default())?;
+
+    let accounts = statements
+        .iter()
+        .filter(|s| s.starts_with("INSERT INTO \"ACCOUNTSTABLE\""))
+        .collect::<Vec<_>>();
+
+    assert_eq!(value_of(accounts[2], 4), "TWD");
+    assert_eq!(
+        check_integrity(&data),
+        vec![IntegrityIssue::UnknownCurrency {
+            entity: "account",
+            id: 3,
+            currency_id: 9,
+        }]
+    );
+
+    Ok(())
+}
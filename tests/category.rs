@@ -0,0 +1,128 @@
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+// Food > Restaurant > Lunch, Food > Grocery, Salary
+fn categories() -> Vec<FinancistoCategory> {
+    vec![
+        category(3, "Lunch", 3, 4),
+        category(1, "Food", 1, 8),
+        category(2, "Restaurant", 2, 5),
+        category(4, "Grocery", 6, 7),
+        category(5, "Salary", 9, 10),
+    ]
+}
+
+#[test]
+fn build_tree_from_nested_set() {
+    let categories = categories();
+    let tree = CategoryTree::new(&categories);
+
+    assert_eq!(
+        tree.roots().iter().map(|c| c.id).collect::<Vec<_>>(),
+        vec![1, 5]
+    );
+    assert_eq!(tree.parent(3).unwrap().id, 2);
+    assert_eq!(tree.parent(2).unwrap().id, 1);
+    assert!(tree.parent(1).is_none());
+    assert_eq!(
+        tree.children(1).iter().map(|c| c.id).collect::<Vec<_>>(),
+        vec![2, 4]
+    );
+    assert_eq!(
+        tree.path(3).iter().map(|c| c.id).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(tree.depth(3), 2);
+    assert_eq!(tree.root(3).unwrap().id, 1);
+}
+
+fn flattened(strategy: CategoryFlatten) -> (Vec<i32>, Vec<(i32, String, i32)>) {
+    let categories = categories();
+    let tree = CategoryTree::new(&categories);
+    let flat = tree.flatten(strategy);
+
+    (
+        flat.parents.iter().map(|c| c.id).collect(),
+        flat.children
+            .iter()
+            .map(|c| (c.category.id, c.name.to_owned(), c.parent.id))
+            .collect(),
+    )
+}
+
+#[test]
+fn flatten_to_nearest_parent() {
+    let (parents, children) = flattened(CategoryFlatten::NearestParent);
+
+    assert_eq!(parents, vec![1, 2, 5]);
+    assert_eq!(
+        children,
+        vec![
+            (1, "Food".to_owned(), 1),
+            (2, "Restaurant".to_owned(), 2),
+            (3, "Lunch".to_owned(), 2),
+            (4, "Grocery".to_owned(), 1),
+            (5, "Salary".to_owned(), 5),
+        ]
+    );
+}
+
+#[test]
+fn flatten_to_top_level() {
+    let (parents, children) = flattened(CategoryFlatten::TopLevel);
+
+    assert_eq!(parents, vec![1, 5]);
+    assert_eq!(
+        children,
+        vec![
+            (1, "Food".to_owned(), 1),
+            (2, "Restaurant".to_owned(), 1),
+            (3, "Lunch".to_owned(), 1),
+            (4, "Grocery".to_owned(), 1),
+            (5, "Salary".to_owned(), 5),
+        ]
+    );
+}
+
+#[test]
+fn flatten_to_joined_names() {
+    let (parents, children) = flattened(CategoryFlatten::Joined);
+
+    assert_eq!(parents, vec![1, 5]);
+    assert_eq!(
+        children,
+        vec![
+            (1, "Food".to_owned(), 1),
+            (2, "Restaurant".to_owned(), 1),
+            (3, "Restaurant:Lunch".to_owned(), 1),
+            (4, "Grocery".to_owned(), 1),
+            (5, "Salary".to_owned(), 5),
+        ]
+    );
+}
+
+#[test]
+fn migrate_nested_categories() -> Result<()> {
+    let data = Financisto {
+        categories: categories(),
+        ..Default::default()
+    };
+    let statements = migrate_categories(&data, &MigrateOptions::default())?;
+
+    assert_eq!(
+        statements
+            .iter()
+            .filter(|s| s.contains("PARENTCATEGORYTABLE"))
+            .count(),
+        3 + 2 // plus uncategorized income and expense
+    );
+    // Lunch (3 + 19) lives under Restaurant (2 + 19)
+    assert!(statements
+        .iter()
+        .any(|s| s.contains("VALUES ('22', 'Lunch', '21'")));
+
+    Ok(())
+}
@@ -0,0 +1,354 @@
+use financisto2bluecoins::*;
+
+/// 2022-12-01 09:00 in Taipei, when the first built transaction happens.
+const START: i64 = 1669856400000;
+/// Time between two built transactions, an hour.
+const STEP: i64 = 3600000;
+
+/// Builds Financisto data in code, in place of a personal backup.
+///
+/// IDs start at 1 per kind, categories are created from `Parent:Child` paths, and every
+/// transaction happens an hour after the one built before it.
+pub struct BackupBuilder {
+    data: Financisto,
+    /// Path of each category, by ID.
+    paths: Vec<(i32, String)>,
+    datetime: i64,
+}
+
+impl BackupBuilder {
+    pub fn new() -> Self {
+        Self {
+            data: Financisto::new(),
+            paths: Vec::new(),
+            datetime: START,
+        }
+    }
+
+    pub fn currency(&mut self, name: &str) -> i32 {
+        let id = self.data.currencies.len() as i32 + 1;
+        let mut currency = super::currency(id, name);
+        currency.is_default = (id == 1) as i32;
+        self.data.currencies.push(currency);
+        id
+    }
+
+    pub fn account(&mut self, title: &str, r#type: &str, currency_id: i32) -> i32 {
+        let id = self.data.accounts.len() as i32 + 1;
+        let mut account = super::account(id, title, r#type);
+        account.currency_id = currency_id;
+        account.sort_order = id;
+        self.data.accounts.push(account);
+        id
+    }
+
+    /// Category of a `Parent:Child` path, creating the missing levels.
+    pub fn category(&mut self, path: &str) -> i32 {
+        let mut prefix = String::new();
+        let mut id = NO_CATEGORY_ID;
+        for title in path.split(':') {
+            if !prefix.is_empty() {
+                prefix.push(':');
+            }
+            prefix.push_str(title);
+
+            id = match self.paths.iter().find(|(_, p)| *p == prefix) {
+                Some((id, _)) => *id,
+                None => {
+                    let id = self.paths.len() as i32 + 1;
+                    self.paths.push((id, prefix.to_owned()));
+                    self.data.categories.push(super::category(id, title, 0, 0));
+                    id
+                }
+            };
+        }
+        id
+    }
+
+    pub fn payee(&mut self, title: &str) -> i32 {
+        let id = self.data.payees.len() as i32 + 1;
+        self.data.payees.push(super::payee(id, title));
+        id
+    }
+
+    pub fn project(&mut self, title: &str) -> i32 {
+        let id = self.data.projects.len() as i32 + 1;
+        self.data.projects.push(FinancistoProject {
+            id,
+            title: title.to_owned(),
+            is_active: 1,
+            updated_on: 0,
+            entity: "project".to_owned(),
+        });
+        id
+    }
+
+    /// Income or expense of the account, in the currency of the account.
+    pub fn transaction(
+        &mut self,
+        account_id: i32,
+        category_id: i32,
+        amount: i64,
+    ) -> &mut FinancistoTransaction {
+        let id = self.data.transactions.len() as i32 + 1;
+        let mut tx = super::transaction(id, account_id, 0, category_id, amount);
+        tx.datetime = self.datetime;
+        tx.original_currency_id = self.currency_of(account_id);
+        self.datetime += STEP;
+
+        self.data.transactions.push(tx);
+        self.data.transactions.last_mut().unwrap()
+    }
+
+    /// Move a positive amount from one account to another.
+    pub fn transfer(&mut self, from: i32, to: i32, amount: i64) -> &mut FinancistoTransaction {
+        let tx = self.transaction(from, NO_CATEGORY_ID, -amount);
+        tx.to_account_id = to;
+        tx.to_amount = amount;
+        tx
+    }
+
+    /// A split of the account without lines, see [`BackupBuilder::split_line`].
+    pub fn split(&mut self, account_id: i32) -> &mut FinancistoTransaction {
+        self.transaction(account_id, SPLIT_CATEGORY_ID, 0)
+    }
+
+    /// A line of the split, at the time of the split, whose amount adds to the split.
+    pub fn split_line(
+        &mut self,
+        parent_id: i32,
+        category_id: i32,
+        amount: i64,
+    ) -> &mut FinancistoTransaction {
+        // IDs are positions from 1
+        let parent = &mut self.data.transactions[parent_id as usize - 1];
+        parent.from_amount += amount;
+        let (account_id, datetime) = (parent.from_account_id, parent.datetime);
+
+        let tx = self.transaction(account_id, category_id, amount);
+        tx.parent_id = parent_id;
+        tx.datetime = datetime;
+        tx
+    }
+
+    /// A scheduled transaction, e.g. `2022-12-01T09:00:00~MONTHLY:interval@1#~INDEFINETELY:null`.
+    pub fn schedule(
+        &mut self,
+        account_id: i32,
+        category_id: i32,
+        amount: i64,
+        recurrence: &str,
+    ) -> &mut FinancistoTransaction {
+        let tx = self.transaction(account_id, category_id, amount);
+        tx.is_template = 2;
+        tx.recurrence = Some(recurrence.to_owned());
+        tx
+    }
+
+    fn currency_of(&self, account_id: i32) -> i32 {
+        self.data
+            .accounts
+            .get(account_id as usize - 1)
+            .map(|a| a.currency_id)
+            .unwrap_or_default()
+    }
+
+    /// The data, with the nested set of the categories filled in.
+    pub fn build(mut self) -> Financisto {
+        let mut paths = self.paths.clone();
+        paths.sort_by(|a, b| a.1.split(':').cmp(b.1.split(':')));
+
+        // in path order every category comes right after its parent and before its siblings
+        let mut next = 1;
+        let mut open: Vec<(&str, usize)> = Vec::new();
+        let mut nested: Vec<(i32, i32, i32)> = Vec::new();
+        for (id, path) in paths.iter() {
+            while let Some((parent, index)) = open.last() {
+                if path.starts_with(&format!("{}:", parent)) {
+                    break;
+                }
+                nested[*index].2 = next;
+                next += 1;
+                open.pop();
+            }
+            open.push((path, nested.len()));
+            nested.push((*id, next, 0));
+            next += 1;
+        }
+        while let Some((_, index)) = open.pop() {
+            nested[index].2 = next;
+            next += 1;
+        }
+
+        for (id, left, right) in nested {
+            let category = self
+                .data
+                .categories
+                .iter_mut()
+                .find(|c| c.id == id)
+                .unwrap();
+            category.left = left;
+            category.right = right;
+        }
+        self.data
+            .categories
+            .insert(0, super::category(NO_CATEGORY_ID, "<NO_CATEGORY>", 0, 0));
+
+        super::with_balances(self.data)
+    }
+}
+
+/// The data as a decompressed backup, each modeled field written the way Financisto does.
+pub fn backup_text(data: &Financisto) -> String {
+    let mut text = String::from(
+        "PACKAGE:ru.orangesoft.financisto\nVERSION_CODE:117\nVERSION_NAME:1.8.4\nDATABASE_VERSION:218\n#START\n",
+    );
+    let mut entity = |kind: &str, fields: &[(&str, Option<String>)]| {
+        text.push_str(&format!("$ENTITY:{}\n", kind));
+        // NULL values are left out
+        for (name, value) in fields {
+            if let Some(value) = value {
+                text.push_str(&format!("{}:{}\n", name, value));
+            }
+        }
+        text.push_str("$$\n");
+    };
+    let some = |value: &dyn ToString| Some(value.to_string());
+
+    for c in data.currencies.iter() {
+        entity(
+            "currency",
+            &[
+                ("_id", some(&c.id)),
+                ("name", some(&c.name)),
+                ("title", some(&c.title)),
+                ("symbol", some(&c.symbol)),
+                ("is_default", some(&c.is_default)),
+                ("decimals", some(&c.decimals)),
+                ("decimal_separator", c.decimal_separator.clone()),
+                ("group_separator", c.group_separator.clone()),
+                ("symbol_format", some(&c.symbol_format)),
+                ("is_active", some(&c.is_active)),
+                ("updated_on", some(&c.updated_on)),
+            ],
+        );
+    }
+    for a in data.accounts.iter() {
+        entity(
+            "account",
+            &[
+                ("_id", some(&a.id)),
+                ("title", some(&a.title)),
+                ("creation_date", some(&a.creation_date)),
+                ("currency_id", some(&a.currency_id)),
+                ("total_amount", some(&a.total_amount)),
+                ("type", some(&a.r#type)),
+                ("issuer", a.issuer.clone()),
+                ("card_issuer", a.card_issuer.clone()),
+                ("note", a.note.clone()),
+                ("sort_order", some(&a.sort_order)),
+                ("is_active", some(&a.is_active)),
+                ("is_include_into_totals", some(&a.is_include_into_totals)),
+                ("last_category_id", some(&a.last_category_id)),
+                ("last_account_id", some(&a.last_account_id)),
+                ("total_limit", some(&a.total_limit)),
+                ("closing_day", some(&a.closing_day)),
+                ("payment_day", some(&a.payment_day)),
+                ("last_transaction_date", some(&a.last_transaction_date)),
+                ("updated_on", some(&a.updated_on)),
+            ],
+        );
+    }
+    for c in data.categories.iter() {
+        entity(
+            "category",
+            &[
+                ("_id", some(&c.id)),
+                ("title", some(&c.title)),
+                ("left", some(&c.left)),
+                ("right", some(&c.right)),
+                ("last_location_id", some(&c.last_location_id)),
+                ("last_project_id", some(&c.last_project_id)),
+                ("type", some(&c.r#type)),
+                ("is_active", some(&c.is_active)),
+                ("updated_on", some(&c.updated_on)),
+            ],
+        );
+    }
+    for p in data.payees.iter() {
+        entity(
+            "payee",
+            &[
+                ("_id", some(&p.id)),
+                ("title", some(&p.title)),
+                ("last_category_id", some(&p.last_category_id)),
+                ("is_active", some(&p.is_active)),
+                ("updated_on", some(&p.updated_on)),
+            ],
+        );
+    }
+    for p in data.projects.iter() {
+        entity(
+            "project",
+            &[
+                ("_id", some(&p.id)),
+                ("title", some(&p.title)),
+                ("is_active", some(&p.is_active)),
+                ("updated_on", some(&p.updated_on)),
+            ],
+        );
+    }
+    for l in data.locations.iter() {
+        entity(
+            "locations",
+            &[
+                ("_id", some(&l.id)),
+                ("name", some(&l.name)),
+                ("datetime", some(&l.datetime)),
+                ("provider", l.provider.clone()),
+                ("accuracy", some(&l.accuracy)),
+                ("latitude", some(&l.latitude)),
+                ("longitude", some(&l.longitude)),
+                ("is_payee", some(&l.is_payee)),
+                ("resolved_address", l.resolved_address.clone()),
+                ("count", some(&l.count)),
+                ("updated_on", some(&l.updated_on)),
+            ],
+        );
+    }
+    for t in data.transactions.iter() {
+        entity(
+            "transactions",
+            &[
+                ("_id", some(&t.id)),
+                ("from_account_id", some(&t.from_account_id)),
+                ("to_account_id", some(&t.to_account_id)),
+                ("category_id", some(&t.category_id)),
+                ("project_id", some(&t.project_id)),
+                ("location_id", some(&t.location_id)),
+                ("payee_id", some(&t.payee_id)),
+                ("parent_id", some(&t.parent_id)),
+                ("note", t.note.clone()),
+                ("from_amount", some(&t.from_amount)),
+                ("to_amount", some(&t.to_amount)),
+                ("datetime", some(&t.datetime)),
+                ("original_currency_id", some(&t.original_currency_id)),
+                ("original_from_amount", some(&t.original_from_amount)),
+                ("template_name", t.template_name.clone()),
+                ("recurrence", t.recurrence.clone()),
+                ("provider", t.provider.clone()),
+                ("accuracy", some(&t.accuracy)),
+                ("latitude", some(&t.latitude)),
+                ("longitude", some(&t.longitude)),
+                ("is_template", some(&t.is_template)),
+                ("status", t.status.clone()),
+                ("is_ccard_payment", some(&t.is_ccard_payment)),
+                ("last_recurrence", some(&t.last_recurrence)),
+                ("updated_on", some(&t.updated_on)),
+            ],
+        );
+    }
+
+    text.push_str("#END\n");
+    text
+}
@@ -0,0 +1,148 @@
+#![allow(dead_code, unused_imports)]
+
+use financisto2bluecoins::*;
+
+mod backup;
+
+pub use backup::*;
+
+pub fn currency(id: i32, name: &str) -> FinancistoCurrency {
+    FinancistoCurrency {
+        id,
+        decimal_separator: None,
+        updated_on: 0,
+        title: name.to_owned(),
+        entity: "currency".to_owned(),
+        decimals: 2,
+        symbol: "$".to_owned(),
+        is_active: 1,
+        name: name.to_owned(),
+        is_default: 1,
+        symbol_format: "RS".to_owned(),
+        group_separator: None,
+    }
+}
+
+pub fn account(id: i32, title: &str, r#type: &str) -> FinancistoAccount {
+    FinancistoAccount {
+        id,
+        note: None,
+        total_limit: 0,
+        entity: "account".to_owned(),
+        total_amount: 0,
+        last_transaction_date: 0,
+        payment_day: 0,
+        sort_order: 0,
+        r#type: r#type.to_owned(),
+        updated_on: 0,
+        is_active: 1,
+        issuer: None,
+        title: title.to_owned(),
+        creation_date: 1669800000000,
+        last_account_id: 0,
+        last_category_id: 0,
+        currency_id: 1,
+        closing_day: 0,
+        card_issuer: None,
+        is_include_into_totals: 1,
+    }
+}
+
+pub fn payee(id: i32, title: &str) -> FinancistoPayee {
+    FinancistoPayee {
+        id,
+        title: title.to_owned(),
+        last_category_id: 0,
+        is_active: 1,
+        updated_on: 0,
+        entity: "payee".to_owned(),
+    }
+}
+
+pub fn category(id: i32, title: &str, left: i32, right: i32) -> FinancistoCategory {
+    FinancistoCategory {
+        r#type: 0,
+        id,
+        updated_on: 0,
+        right,
+        left,
+        last_location_id: 0,
+        is_active: 1,
+        title: title.to_owned(),
+        last_project_id: 0,
+        entity: "category".to_owned(),
+    }
+}
+
+pub fn transaction(
+    id: i32,
+    from_account_id: i32,
+    to_account_id: i32,
+    category_id: i32,
+    amount: i64,
+) -> FinancistoTransaction {
+    FinancistoTransaction {
+        longitude: 0.0,
+        from_account_id,
+        location_id: 0,
+        note: None,
+        to_account_id,
+        entity: "transactions".to_owned(),
+        datetime: 1669900000000 + id as i64 * 1000,
+        project_id: 0,
+        provider: None,
+        template_name: None,
+        recurrence: None,
+        latitude: 0.0,
+        accuracy: 0.0,
+        status: None,
+        is_ccard_payment: 0,
+        payee_id: 0,
+        category_id,
+        parent_id: 0,
+        to_amount: if to_account_id == 0 { 0 } else { -amount },
+        from_amount: amount,
+        original_currency_id: 1,
+        original_from_amount: 0,
+        last_recurrence: 0,
+        is_template: 0,
+        updated_on: 0,
+        id,
+    }
+}
+
+/// A small book: Cash and Bank accounts, Food > Lunch and 收入 categories, one payee.
+pub fn financisto(transactions: Vec<FinancistoTransaction>) -> Financisto {
+    let mut data = Financisto::new();
+    data.currencies.push(currency(1, "TWD"));
+    data.accounts.push(account(1, "Cash", "CASH"));
+    data.accounts.push(account(2, "Bank", "BANK"));
+    data.categories.push(category(0, "<NO_CATEGORY>", 0, 0));
+    data.categories.push(category(1, "Food", 1, 4));
+    data.categories.push(category(2, "Lunch", 2, 3));
+    data.categories.push(category(3, "收入", 5, 6));
+    data.payees.push(payee(1, "7-Eleven"));
+    data.transactions = transactions;
+    with_balances(data)
+}
+
+/// The data with the balance of each account stored the way Financisto does.
+pub fn with_balances(mut data: Financisto) -> Financisto {
+    let balances = data.balances();
+    for account in data.accounts.iter_mut() {
+        account.total_amount = balances[&account.id];
+    }
+    data
+}
+
+/// Value at the given column position of an INSERT statement.
+pub fn value_of(statement: &str, index: usize) -> &str {
+    statement
+        .split("VALUES (")
+        .nth(1)
+        .unwrap()
+        .split(", ")
+        .nth(index)
+        .unwrap()
+        .trim_matches(|c| c == '\'' || c == ')' || c == ';')
+}
@@ -0,0 +1,233 @@
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+/// Create the table with the columns of the row if needed, and insert the row.
+fn insert(connection: &Connection, table: &str, row: &[(&str, Value)]) -> Result<()> {
+    let columns = row
+        .iter()
+        .map(|(c, _)| format!("\"{}\"", c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    connection.execute(
+        &format!("CREATE TABLE IF NOT EXISTS \"{}\" ({})", table, columns),
+        [],
+    )?;
+
+    let placeholders = vec!["?"; row.len()].join(", ");
+    connection.execute(
+        &format!(
+            "INSERT INTO \"{}\" ({}) VALUES ({})",
+            table, columns, placeholders
+        ),
+        rusqlite::params_from_iter(row.iter().map(|(_, v)| v)),
+    )?;
+
+    Ok(())
+}
+
+fn int(value: i64) -> Value {
+    Value::Integer(value)
+}
+
+fn text(value: &str) -> Value {
+    Value::Text(value.to_owned())
+}
+
+fn financisto_db(path: &std::path::Path) -> Result<()> {
+    let connection = Connection::open(path)?;
+
+    insert(
+        &connection,
+        "currency",
+        &[
+            ("_id", int(1)),
+            ("name", text("TWD")),
+            ("title", text("New Taiwan Dollar")),
+            ("symbol", text("$")),
+            ("symbol_format", text("RS")),
+            ("decimals", int(2)),
+            ("decimal_separator", Value::Null),
+            ("group_separator", Value::Null),
+            ("is_default", int(1)),
+            ("is_active", int(1)),
+            ("updated_on", int(0)),
+        ],
+    )?;
+    for (id, title, r#type) in [(1, "Cash", "CASH"), (2, "Bank", "BANK")] {
+        insert(
+            &connection,
+            "account",
+            &[
+                ("_id", int(id)),
+                ("title", text(title)),
+                ("type", text(r#type)),
+                ("note", Value::Null),
+                ("creation_date", int(1669800000000)),
+                ("currency_id", int(1)),
+                ("total_amount", int(0)),
+                ("total_limit", int(0)),
+                ("last_transaction_date", int(0)),
+                ("payment_day", int(0)),
+                ("closing_day", int(0)),
+                ("sort_order", int(0)),
+                ("is_active", int(1)),
+                ("is_include_into_totals", int(1)),
+                ("issuer", Value::Null),
+                ("card_issuer", Value::Null),
+                ("last_account_id", int(0)),
+                ("last_category_id", int(0)),
+                ("updated_on", int(0)),
+            ],
+        )?;
+    }
+    for (id, title, left, right) in [(0, "<NO_CATEGORY>", 0, 0), (1, "Food", 1, 2)] {
+        insert(
+            &connection,
+            "category",
+            &[
+                ("_id", int(id)),
+                ("title", text(title)),
+                ("left", int(left)),
+                ("right", int(right)),
+                ("type", int(0)),
+                ("is_active", int(1)),
+                ("last_location_id", int(0)),
+                ("last_project_id", int(0)),
+                ("updated_on", int(0)),
+            ],
+        )?;
+    }
+    insert(
+        &connection,
+        "payee",
+        &[
+            ("_id", int(1)),
+            ("title", text("7-Eleven")),
+            ("last_category_id", int(1)),
+            ("is_active", int(1)),
+            ("updated_on", int(0)),
+        ],
+    )?;
+    insert(
+        &connection,
+        "project",
+        &[
+            ("_id", int(1)),
+            ("title", text("Trip")),
+            ("is_active", int(1)),
+            ("updated_on", int(0)),
+        ],
+    )?;
+    insert(
+        &connection,
+        "locations",
+        &[
+            ("_id", int(1)),
+            ("name", text("Taipei 101")),
+            ("datetime", int(1669800000000)),
+            ("provider", text("gps")),
+            ("accuracy", Value::Real(5.0)),
+            ("latitude", Value::Real(25.0339)),
+            ("longitude", Value::Real(121.5645)),
+            ("is_payee", int(0)),
+            ("resolved_address", Value::Null),
+            ("count", int(1)),
+            ("updated_on", int(0)),
+        ],
+    )?;
+    for (id, from, to, category, amount, note) in [
+        (1, 1, 0, 1, -12000, Value::Text("lunch".to_owned())),
+        (2, 1, 2, 0, -5000, Value::Null),
+    ] {
+        insert(
+            &connection,
+            "transactions",
+            &[
+                ("_id", int(id)),
+                ("from_account_id", int(from)),
+                ("to_account_id", int(to)),
+                ("category_id", int(category)),
+                ("project_id", int(1)),
+                ("location_id", int(1)),
+                ("payee_id", int(1)),
+                ("parent_id", int(0)),
+                ("note", note),
+                ("from_amount", int(amount)),
+                ("to_amount", int(if to == 0 { 0 } else { -amount })),
+                ("datetime", int(1669900000000 + id * 1000)),
+                ("original_currency_id", int(1)),
+                ("original_from_amount", int(0)),
+                ("template_name", Value::Null),
+                ("recurrence", Value::Null),
+                ("is_template", int(0)),
+                ("is_ccard_payment", int(0)),
+                ("status", text("UR")),
+                ("provider", Value::Null),
+                ("accuracy", Value::Real(0.0)),
+                ("latitude", Value::Real(0.0)),
+                ("longitude", Value::Real(0.0)),
+                ("last_recurrence", int(0)),
+                ("updated_on", int(0)),
+            ],
+        )?;
+    }
+    insert(
+        &connection,
+        "budget",
+        &[
+            ("_id", int(1)),
+            ("title", text("Food")),
+            ("amount", int(300000)),
+        ],
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn read_financisto_database() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("financisto-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    financisto_db(&path)?;
+
+    let data = read_database(&path);
+    std::fs::remove_file(&path)?;
+    let data = data?;
+
+    assert_eq!(data.currencies[0].name, "TWD");
+    assert_eq!(data.currencies[0].decimal_separator, None);
+    assert_eq!(data.accounts.len(), 2);
+    assert_eq!(data.accounts[0].entity, "account");
+    assert_eq!(data.categories[1].title, "Food");
+    assert_eq!(data.payees[0].title, "7-Eleven");
+    assert_eq!(data.projects[0].title, "Trip");
+    assert_eq!(data.locations[0].name, "Taipei 101");
+    assert_eq!(data.locations[0].latitude, 25.0339);
+    assert_eq!(data.transactions.len(), 2);
+    assert_eq!(data.transactions[0].note.as_deref(), Some("lunch"));
+    assert_eq!(data.transactions[1].to_amount, 5000);
+    assert_eq!(
+        data.raw.get("budget", 1).unwrap().get("amount"),
+        Some("300000")
+    );
+    assert_eq!(data.raw.of_type("transactions").len(), 2);
+
+    // the migrators do not care where the data came from
+    let expected = financisto(vec![]);
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+    assert_eq!(value_of(&statements[0], 1), "lunch");
+    assert_eq!(statements.len(), 1 + 1 + 1 + 2);
+    assert!(check_integrity(&data).is_empty());
+    assert_eq!(
+        migrate_accounts(&data, &MigrateOptions::default())?,
+        migrate_accounts(&expected, &MigrateOptions::default())?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use chrono::NaiveDate;
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+fn rows(statements: &[String], table: &str) -> Vec<String> {
+    statements
+        .iter()
+        .filter(|s| s.starts_with(&format!("INSERT INTO \"{}\"", table)))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn date_range_keeps_opening_balance() -> Result<()> {
+    let early = transaction(1, 1, 0, 2, -100);
+    let mut late = transaction(2, 1, 0, 2, -200);
+    late.datetime += 5 * DAY;
+    let data = financisto(vec![early, late]);
+    let options = MigrateOptions {
+        filter: Filter {
+            from: NaiveDate::from_ymd_opt(2022, 12, 4),
+            ..Default::default()
+        },
+        timezone: "Asia/Taipei".parse()?,
+        ..Default::default()
+    };
+
+    let transactions = rows(&migrate_transactions(&data, &options)?, "TRANSACTIONSTABLE");
+    let accounts = migrate_accounts(&data, &options)?;
+
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(value_of(&transactions[0], 2), "-2000000");
+    // init transaction of Cash carries what happened before the range
+    let init = rows(&accounts, "TRANSACTIONSTABLE");
+    assert_eq!(value_of(&init[0], 2), "-1000000");
+    assert_eq!(value_of(&init[1], 2), "0");
+
+    Ok(())
+}
+
+#[test]
+fn transfers_to_filtered_accounts_become_income_or_expense() -> Result<()> {
+    let data = financisto(vec![
+        transaction(1, 1, 2, 0, -100),
+        transaction(2, 2, 1, 0, -300),
+        transaction(3, 2, 0, 2, -500),
+    ]);
+    let options = MigrateOptions {
+        item_names: ItemNames::for_language("en"),
+        filter: Filter {
+            accounts: Selection {
+                include: vec!["Cash".to_owned()],
+                exclude: vec![],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let ids = CategoryIds::new(&data.categories);
+
+    let statements = migrate_transactions(&data, &options)?;
+    let transactions = rows(&statements, "TRANSACTIONSTABLE");
+    let accounts = rows(&migrate_accounts(&data, &options)?, "ACCOUNTSTABLE");
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(value_of(&accounts[0], 1), "Cash");
+
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(value_of(&transactions[0], 6), TYPE_EXPENSE.to_string());
+    assert_eq!(value_of(&transactions[0], 8), "6");
+    assert_eq!(
+        value_of(&transactions[0], 7),
+        ids.uncategorized_expense.to_string()
+    );
+    assert_eq!(value_of(&transactions[1], 6), TYPE_INCOME.to_string());
+    assert_eq!(value_of(&transactions[1], 2), "3000000");
+    assert_eq!(value_of(&transactions[1], 8), "6");
+    assert_eq!(value_of(&rows(&statements, "ITEMTABLE")[0], 1), "Transfer");
+    // the detached transfers are uncategorized
+    let categories = rows(&migrate_categories(&data, &options)?, "CHILDCATEGORYTABLE");
+    assert!(categories
+        .iter()
+        .any(|c| value_of(c, 0) == ids.uncategorized_expense.to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn only_referenced_categories_are_emitted() -> Result<()> {
+    let data = financisto(vec![
+        transaction(1, 1, 0, 2, -100),
+        transaction(2, 1, 0, 3, 200),
+        transaction(3, 1, 0, 0, -300),
+    ]);
+    let options = MigrateOptions {
+        filter: Filter {
+            categories: Selection {
+                include: vec!["Food".to_owned()],
+                exclude: vec![],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let transactions = rows(&migrate_transactions(&data, &options)?, "TRANSACTIONSTABLE");
+    let categories = migrate_categories(&data, &options)?;
+
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(value_of(&transactions[0], 7), "21");
+    // Food > Lunch, and no Uncategorized as no migrated row uses them
+    let children = rows(&categories, "CHILDCATEGORYTABLE");
+    assert_eq!(value_of(&children[0], 1), "Lunch");
+    assert_eq!(children.len(), 1);
+    assert_eq!(rows(&categories, "PARENTCATEGORYTABLE").len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn empty_filter_keeps_unknown_accounts() -> Result<()> {
+    let data = financisto(vec![
+        transaction(1, 9, 0, 2, -100),
+        transaction(2, 1, 9, 0, -300),
+    ]);
+
+    let transactions = rows(
+        &migrate_transactions(&data, &MigrateOptions::default())?,
+        "TRANSACTIONSTABLE",
+    );
+
+    assert_eq!(transactions.len(), 3);
+    assert_eq!(value_of(&transactions[0], 8), "14");
+    assert_eq!(value_of(&transactions[1], 6), TYPE_TRANSFER.to_string());
+    assert_eq!(value_of(&transactions[2], 8), "14");
+
+    Ok(())
+}
+
+#[test]
+fn unknown_account_is_rejected() {
+    let data = financisto(vec![]);
+    let filter = Filter {
+        accounts: Selection {
+            include: vec![],
+            exclude: vec!["Wallet".to_owned()],
+        },
+        ..Default::default()
+    };
+
+    let error = filter.resolve(&data, Timezone::Local).err().unwrap();
+
+    assert_eq!(error.to_string(), "unknown account 'Wallet'");
+}
+
+#[test]
+fn last_date_out_of_range_is_rejected() {
+    let data = financisto(vec![]);
+    let filter = Filter {
+        to: Some(NaiveDate::MAX),
+        ..Default::default()
+    };
+
+    let error = filter.resolve(&data, Timezone::Local).err().unwrap();
+
+    assert!(error.to_string().starts_with("date out of range"));
+}
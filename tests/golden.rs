@@ -0,0 +1,197 @@
+use std::fs;
+use std::path::Path;
+
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+/// Every rule of the migration in one book: nested categories, a foreign currency account and
+/// purchase, payees and statuses, splits with a transfer line, transfers, a credit card payment,
+/// scheduled transactions and notes that need escaping.
+fn book() -> Financisto {
+    let mut book = BackupBuilder::new();
+    let twd = book.currency("TWD");
+    let usd = book.currency("USD");
+    let cash = book.account("Cash", "CASH", twd);
+    let bank = book.account("Bank", "BANK", twd);
+    let card = book.account("Visa", "CREDIT_CARD", twd);
+    let dollars = book.account("Dollars", "BANK", usd);
+
+    let lunch = book.category("Food:Restaurant:Lunch");
+    let dinner = book.category("Food:Restaurant:Dinner");
+    let grocery = book.category("Food:Grocery");
+    let salary = book.category("收入:Salary");
+    let rent = book.category("Home:Rent");
+    let seven = book.payee("7-Eleven");
+    let trip = book.project("Trip");
+
+    book.transaction(bank, salary, 5000000).note = Some("December".to_owned());
+    book.transfer(bank, cash, 300000).note = Some("ATM".to_owned());
+    let tx = book.transaction(cash, lunch, -12000);
+    tx.payee_id = seven;
+    tx.status = Some("RC".to_owned());
+    tx.note = Some("rice & tea, it's \"good\"\nsecond line: fine".to_owned());
+    book.transaction(cash, dinner, -35000).project_id = trip;
+    book.transaction(cash, NO_CATEGORY_ID, -5000);
+    book.transaction(cash, NO_CATEGORY_ID, 2000);
+    let tx = book.transaction(card, grocery, -150000);
+    tx.status = Some("CL".to_owned());
+    tx.payee_id = seven;
+
+    let split = book.split(bank);
+    split.note = Some("weekly shopping".to_owned());
+    let split = split.id;
+    book.split_line(split, grocery, -80000).note = Some("vegetables".to_owned());
+    book.split_line(split, dinner, -20000);
+    book.split_line(split, lunch, 5000).note = Some("refund".to_owned());
+    let line = book.split_line(split, NO_CATEGORY_ID, -100000);
+    line.to_account_id = cash;
+    line.to_amount = 100000;
+
+    book.transfer(bank, card, 150000).is_ccard_payment = 1;
+    book.transfer(bank, dollars, 310000).to_amount = 10000;
+    let tx = book.transaction(cash, dinner, -31000);
+    tx.original_currency_id = usd;
+    tx.original_from_amount = -1000;
+
+    book.schedule(
+        bank,
+        rent,
+        -1800000,
+        "2022-12-05T09:00:00~MONTHLY:interval@1#~EXACTLY_TIMES:12",
+    );
+    book.schedule(
+        dollars,
+        salary,
+        20000,
+        "2022-12-31T10:00:00~GEEKY:FREQ=YEARLY;INTERVAL=1#~INDEFINETELY:null",
+    )
+    .note = Some("bonus".to_owned());
+
+    book.build()
+}
+
+fn options() -> MigrateOptions {
+    MigrateOptions {
+        timezone: "Asia/Taipei".parse().unwrap(),
+        ..Default::default()
+    }
+}
+
+fn migrate(data: &Financisto, options: &MigrateOptions) -> Result<Vec<String>> {
+    let mut statements = migrate_accounts(data, options)?;
+    statements.append(&mut migrate_categories(data, options)?);
+    statements.append(&mut migrate_transactions(data, options)?);
+    Ok(statements)
+}
+
+/// Compare with `tests/golden/<name>`, or rewrite it when `UPDATE_GOLDEN` is set.
+fn assert_golden(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {}, run with UPDATE_GOLDEN=1", path.display(), e));
+    if expected != actual {
+        let line = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e != a)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "{} differs from line {}:\nexpected: {}\n  actual: {}\nrun with UPDATE_GOLDEN=1 if the change is intended",
+            path.display(),
+            line + 1,
+            expected.lines().nth(line).unwrap_or_default(),
+            actual.lines().nth(line).unwrap_or_default()
+        );
+    }
+}
+
+#[test]
+fn backup_round_trip() -> Result<()> {
+    let data = book();
+
+    let parsed = parse_backup(&backup_text(&data))?;
+
+    assert_eq!(
+        format!("{:?}", parsed.transactions),
+        format!("{:?}", data.transactions)
+    );
+    assert_eq!(
+        format!("{:?}", parsed.categories),
+        format!("{:?}", data.categories)
+    );
+    assert_eq!(
+        format!("{:?}", parsed.accounts),
+        format!("{:?}", data.accounts)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn migrate_book() -> Result<()> {
+    let data = parse_backup(&backup_text(&book()))?;
+
+    let statements = migrate(&data, &options())?;
+
+    assert_eq!(verify_sql(&statements)?, vec![]);
+    // one reminder row on the account of each scheduled template, none of them is a transfer
+    let mut reminders = statements
+        .iter()
+        .map(|s| Insert::parse(s))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|row| row.get("reminderTransaction") == Some("1"))
+        .map(|row| row.int("accountID"))
+        .collect::<Vec<_>>();
+    let mut templates = data
+        .transactions
+        .iter()
+        .filter(|t| t.is_template == 2)
+        .map(|t| t.from_account_id as i64 + 5)
+        .collect::<Vec<_>>();
+    reminders.sort();
+    templates.sort();
+    assert_eq!(templates.len(), 2);
+    assert_eq!(reminders, templates);
+    let report = Report::new(&data, &options(), &statements)?;
+    assert_eq!(report.mismatches().count(), 0);
+    assert_golden("book.sql", &(statements.join("\n") + "\n"));
+    assert_golden("book.report.txt", &(report.to_string() + "\n"));
+
+    Ok(())
+}
+
+#[test]
+fn migrate_book_with_options() -> Result<()> {
+    let data = book();
+    let options = MigrateOptions {
+        category_flatten: CategoryFlatten::Joined,
+        split_note: SplitNote::ItemOnly,
+        filter: Filter {
+            accounts: Selection {
+                include: vec![],
+                exclude: vec!["Dollars".to_owned()],
+            },
+            ..Default::default()
+        },
+        ..options()
+    };
+
+    let statements = migrate(&data, &options)?;
+
+    assert_eq!(verify_sql(&statements)?, vec![]);
+    assert_golden("book.joined.sql", &(statements.join("\n") + "\n"));
+
+    Ok(())
+}
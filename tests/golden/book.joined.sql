@@ -0,0 +1,45 @@
+INSERT INTO "ACCOUNTSTABLE" ("accountsTableID", "accountName", "accountTypeID", "accountHidden", "accountCurrency", "accountConversionRateNew", "currencyChanged", "creditLimit", "cutOffDa", "creditCardDueDate", "cashBasedAccounts", "accountSelectorVisibility", "accountsExtraColumnInt1", "accountsExtraColumnInt2", "accountsExtraColumnString1", "accountsExtraColumnString2") VALUES ('6', 'Cash', '4', '0', 'TWD', '1.0', NULL, '0', '0', '0', '0', '0', NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('5', 'Cash', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('6', '5', '0', 'TWD', '1', '2022-11-30 17:20:00', '2', '2', '6', '', '2', '3', '6', '1669800000000', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "ACCOUNTSTABLE" ("accountsTableID", "accountName", "accountTypeID", "accountHidden", "accountCurrency", "accountConversionRateNew", "currencyChanged", "creditLimit", "cutOffDa", "creditCardDueDate", "cashBasedAccounts", "accountSelectorVisibility", "accountsExtraColumnInt1", "accountsExtraColumnInt2", "accountsExtraColumnString1", "accountsExtraColumnString2") VALUES ('7', 'Bank', '3', '0', 'TWD', '1.0', NULL, '0', '0', '0', '0', '0', NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('6', 'Bank', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('7', '6', '0', 'TWD', '1', '2022-11-30 17:20:00', '2', '2', '7', '', '2', '3', '7', '1669800000000', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "ACCOUNTSTABLE" ("accountsTableID", "accountName", "accountTypeID", "accountHidden", "accountCurrency", "accountConversionRateNew", "currencyChanged", "creditLimit", "cutOffDa", "creditCardDueDate", "cashBasedAccounts", "accountSelectorVisibility", "accountsExtraColumnInt1", "accountsExtraColumnInt2", "accountsExtraColumnString1", "accountsExtraColumnString2") VALUES ('8', 'Visa', '8', '0', 'TWD', '1.0', NULL, '0', '0', '0', '0', '0', NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('7', 'Visa', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('8', '7', '0', 'TWD', '1', '2022-11-30 17:20:00', '2', '2', '8', '', '2', '3', '8', '1669800000000', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "PARENTCATEGORYTABLE" ("parentCategoryTableID", "parentCategoryName", "categoryGroupID", "budgetAmountCategoryParent", "budgetCustomSetupParent", "budgetPeriodCategoryParent", "budgetEnabledCategoryParent", "categoryParentExtraColumnInt1", "categoryParentExtraColumnInt2", "categoryParentExtraColumnString1", "categoryParentExtraColumnString2") VALUES ('20', 'Food', '3', NULL, NULL, NULL, '1', NULL, NULL, NULL, NULL);
+INSERT INTO "PARENTCATEGORYTABLE" ("parentCategoryTableID", "parentCategoryName", "categoryGroupID", "budgetAmountCategoryParent", "budgetCustomSetupParent", "budgetPeriodCategoryParent", "budgetEnabledCategoryParent", "categoryParentExtraColumnInt1", "categoryParentExtraColumnInt2", "categoryParentExtraColumnString1", "categoryParentExtraColumnString2") VALUES ('27', 'Home', '3', NULL, NULL, NULL, '1', NULL, NULL, NULL, NULL);
+INSERT INTO "PARENTCATEGORYTABLE" ("parentCategoryTableID", "parentCategoryName", "categoryGroupID", "budgetAmountCategoryParent", "budgetCustomSetupParent", "budgetPeriodCategoryParent", "budgetEnabledCategoryParent", "categoryParentExtraColumnInt1", "categoryParentExtraColumnInt2", "categoryParentExtraColumnString1", "categoryParentExtraColumnString2") VALUES ('25', '收入', '2', NULL, NULL, NULL, '1', NULL, NULL, NULL, NULL);
+INSERT INTO "CHILDCATEGORYTABLE" ("categoryTableID", "childCategoryName", "parentCategoryID", "budgetAmount", "budgetCustomSetup", "budgetPeriod", "budgetEnabledCategoryChild", "childCategoryIcon", "categorySelectorVisibility", "categoryExtraColumnInt1", "categoryExtraColumnInt2", "categoryExtraColumnString1", "categoryExtraColumnString2") VALUES ('24', 'Grocery', '20', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);
+INSERT INTO "CHILDCATEGORYTABLE" ("categoryTableID", "childCategoryName", "parentCategoryID", "budgetAmount", "budgetCustomSetup", "budgetPeriod", "budgetEnabledCategoryChild", "childCategoryIcon", "categorySelectorVisibility", "categoryExtraColumnInt1", "categoryExtraColumnInt2", "categoryExtraColumnString1", "categoryExtraColumnString2") VALUES ('23', 'Restaurant:Dinner', '20', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);
+INSERT INTO "CHILDCATEGORYTABLE" ("categoryTableID", "childCategoryName", "parentCategoryID", "budgetAmount", "budgetCustomSetup", "budgetPeriod", "budgetEnabledCategoryChild", "childCategoryIcon", "categorySelectorVisibility", "categoryExtraColumnInt1", "categoryExtraColumnInt2", "categoryExtraColumnString1", "categoryExtraColumnString2") VALUES ('22', 'Restaurant:Lunch', '20', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);
+INSERT INTO "CHILDCATEGORYTABLE" ("categoryTableID", "childCategoryName", "parentCategoryID", "budgetAmount", "budgetCustomSetup", "budgetPeriod", "budgetEnabledCategoryChild", "childCategoryIcon", "categorySelectorVisibility", "categoryExtraColumnInt1", "categoryExtraColumnInt2", "categoryExtraColumnString1", "categoryExtraColumnString2") VALUES ('28', 'Rent', '27', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);
+INSERT INTO "CHILDCATEGORYTABLE" ("categoryTableID", "childCategoryName", "parentCategoryID", "budgetAmount", "budgetCustomSetup", "budgetPeriod", "budgetEnabledCategoryChild", "childCategoryIcon", "categorySelectorVisibility", "categoryExtraColumnInt1", "categoryExtraColumnInt2", "categoryExtraColumnString1", "categoryExtraColumnString2") VALUES ('26', 'Salary', '25', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);
+INSERT INTO "PARENTCATEGORYTABLE" ("parentCategoryTableID", "parentCategoryName", "categoryGroupID", "budgetAmountCategoryParent", "budgetCustomSetupParent", "budgetPeriodCategoryParent", "budgetEnabledCategoryParent", "categoryParentExtraColumnInt1", "categoryParentExtraColumnInt2", "categoryParentExtraColumnString1", "categoryParentExtraColumnString2") VALUES ('30', 'Uncategorized', '2', NULL, NULL, NULL, '1', NULL, NULL, NULL, NULL);
+INSERT INTO "CHILDCATEGORYTABLE" ("categoryTableID", "childCategoryName", "parentCategoryID", "budgetAmount", "budgetCustomSetup", "budgetPeriod", "budgetEnabledCategoryChild", "childCategoryIcon", "categorySelectorVisibility", "categoryExtraColumnInt1", "categoryExtraColumnInt2", "categoryExtraColumnString1", "categoryExtraColumnString2") VALUES ('30', 'Uncategorized', '30', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);
+INSERT INTO "PARENTCATEGORYTABLE" ("parentCategoryTableID", "parentCategoryName", "categoryGroupID", "budgetAmountCategoryParent", "budgetCustomSetupParent", "budgetPeriodCategoryParent", "budgetEnabledCategoryParent", "categoryParentExtraColumnInt1", "categoryParentExtraColumnInt2", "categoryParentExtraColumnString1", "categoryParentExtraColumnString2") VALUES ('29', 'Uncategorized', '3', NULL, NULL, NULL, '1', NULL, NULL, NULL, NULL);
+INSERT INTO "CHILDCATEGORYTABLE" ("categoryTableID", "childCategoryName", "parentCategoryID", "budgetAmount", "budgetCustomSetup", "budgetPeriod", "budgetEnabledCategoryChild", "childCategoryIcon", "categorySelectorVisibility", "categoryExtraColumnInt1", "categoryExtraColumnInt2", "categoryExtraColumnString1", "categoryExtraColumnString2") VALUES ('29', 'Uncategorized', '29', '0', NULL, '3', '1', NULL, '0', NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('41', 'December', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669856400001', '41', '50000000000', 'TWD', '1', '2022-12-01 09:00:00', '4', '26', '7', '', '0', '1', '7', '1669856400001', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('42', '轉帳', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669860000000', '42', '-3000000000', 'TWD', '1', '2022-12-01 10:00:00', '5', '3', '7', 'ATM', '0', '1', '6', '1669860000001', '6', '0', '1669860000000', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669860000001', '42', '3000000000', 'TWD', '1', '2022-12-01 10:00:00', '5', '3', '6', 'ATM', '0', '2', '7', '1669860000000', '6', '0', '1669860000000', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('43', 'rice & tea, it''s "good"
+second line: fine', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669863600002', '43', '-120000000', 'TWD', '1', '2022-12-01 11:00:00', '3', '22', '6', '', '2', '1', '6', '1669863600002', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669867200003', '2', '-350000000', 'TWD', '1', '2022-12-01 12:00:00', '3', '23', '6', '', '0', '1', '6', '1669867200003', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669870800004', '2', '-50000000', 'TWD', '1', '2022-12-01 13:00:00', '3', '29', '6', '', '0', '1', '6', '1669870800004', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669874400005', '3', '20000000', 'TWD', '1', '2022-12-01 14:00:00', '4', '30', '6', '', '0', '1', '6', '1669874400005', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669878000006', '2', '-1500000000', 'TWD', '1', '2022-12-01 15:00:00', '3', '24', '8', '', '1', '1', '8', '1669878000006', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('44', 'weekly shopping', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669881600008', '44', '-800000000', 'TWD', '1', '2022-12-01 16:00:00', '3', '24', '7', 'vegetables', '0', '1', '7', '1669881600008', '6', '1669881600007', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669881600009', '44', '-200000000', 'TWD', '1', '2022-12-01 16:00:00', '3', '23', '7', '', '0', '1', '7', '1669881600009', '6', '1669881600007', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669881600010', '44', '50000000', 'TWD', '1', '2022-12-01 16:00:00', '4', '22', '7', 'refund', '0', '1', '7', '1669881600010', '6', '1669881600007', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669881600011', '42', '-1000000000', 'TWD', '1', '2022-12-01 16:00:00', '5', '3', '7', '', '0', '1', '6', '1669881600012', '6', '0', '1669881600011', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669881600012', '42', '1000000000', 'TWD', '1', '2022-12-01 16:00:00', '5', '3', '6', '', '0', '2', '7', '1669881600011', '6', '0', '1669881600011', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "ITEMTABLE" ("itemTableID", "itemName", "itemAutoFillVisibility") VALUES ('45', '信用卡繳款', '0');
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669899600000', '45', '-1500000000', 'TWD', '1', '2022-12-01 21:00:00', '5', '3', '7', '', '0', '1', '8', '1669899600001', '6', '0', '1669899600000', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669899600001', '45', '1500000000', 'TWD', '1', '2022-12-01 21:00:00', '5', '3', '8', '', '0', '2', '7', '1669899600000', '6', '0', '1669899600000', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669903200012', '42', '-3100000000', 'TWD', '1', '2022-12-01 22:00:00', '3', '29', '7', '', '0', '1', '7', '1669903200012', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669906800013', '2', '-310000000', 'USD', '0.03225806451612903', '2022-12-01 23:00:00', '3', '23', '6', '', '0', '1', '6', '1669906800013', '6', '0', '0', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO "TRANSACTIONSTABLE" ("transactionsTableID", "itemID", "amount", "transactionCurrency", "conversionRateNew", "date", "transactionTypeID", "categoryID", "accountID", "notes", "status", "accountReference", "accountPairID", "uidPairID", "deletedTransaction", "newSplitTransactionID", "transferGroupID", "reminderTransaction", "reminderGroupID", "reminderFrequency", "reminderRepeatEvery", "reminderEndingType", "reminderStartDate", "reminderEndDate", "reminderAfterNoOfOccurences", "reminderAutomaticLogTransaction", "reminderRepeatByDayOfMonth", "reminderExcludeWeekend", "reminderWeekDayMoveSetting", "reminderUnbilled", "creditCardInstallment", "reminderVersion", "dataExtraColumnString1") VALUES ('1669910400014', '2', '-18000000000', 'TWD', '1', '2022-12-02 00:00:00', '3', '28', '7', '', '0', '1', '7', '1669910400014', '6', '0', '0', '1', '1669910400014', '3', '1', '2', '2022-12-05 09:00:00', NULL, '12', '0', '0', '0', '0', '0', '0', '1', NULL);
@@ -0,0 +1,200 @@
+use financisto2bluecoins::*;
+
+fn options() -> ImportOptions {
+    ImportOptions {
+        timezone: "Asia/Taipei".parse().unwrap(),
+        ..Default::default()
+    }
+}
+
+fn migrate(data: &Financisto) -> Result<Vec<String>> {
+    let options = MigrateOptions::default();
+    let mut statements = migrate_accounts(data, &options)?;
+    statements.append(&mut migrate_categories(data, &options)?);
+    statements.append(&mut migrate_transactions(data, &options)?);
+    Ok(statements)
+}
+
+#[test]
+fn import_csv_export() -> Result<()> {
+    let csv = "\
+date,time,account,amount,currency,category,parent,payee,note
+2022-12-01,12:30:00,Cash,-120.00,TWD,Lunch,Food,7-Eleven,rice
+2022-12-02,09:00:00,Bank,\"1,000.50\",TWD,Salary,,,
+2022-12-03,10:00:00,Bank,-500.00,TWD,[Cash],,,withdraw
+";
+
+    let data = import_csv(csv.as_bytes(), &options())?;
+
+    assert_eq!(data.accounts.len(), 2);
+    assert_eq!(data.payees.len(), 1);
+    let titles = data
+        .categories
+        .iter()
+        .map(|c| (c.title.as_str(), c.left, c.right))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        titles,
+        vec![
+            ("<NO_CATEGORY>", 0, 0),
+            ("Food", 1, 4),
+            ("Lunch", 2, 3),
+            ("Salary", 5, 6)
+        ]
+    );
+
+    let lunch = &data.transactions[0];
+    assert_eq!(lunch.from_amount, -12000);
+    assert_eq!(lunch.category_id, 2);
+    assert_eq!(lunch.note.as_deref(), Some("rice"));
+    assert_eq!(lunch.datetime, 1669869000000);
+    assert_eq!(data.transactions[1].from_amount, 100050);
+
+    let transfer = &data.transactions[2];
+    assert_eq!((transfer.from_account_id, transfer.to_account_id), (2, 1));
+    assert_eq!((transfer.from_amount, transfer.to_amount), (-50000, 50000));
+
+    let statements = migrate(&data)?;
+    assert_eq!(verify_sql(&statements)?, vec![]);
+    assert_eq!(
+        Report::new(&data, &MigrateOptions::default(), &statements)?
+            .mismatches()
+            .count(),
+        0
+    );
+
+    Ok(())
+}
+
+#[test]
+fn import_qif_export() -> Result<()> {
+    let qif = "\
+!Type:Cat
+NFood:Lunch
+E
+^
+!Account
+NCash
+TCash
+^
+!Type:Cash
+D01/12/2022
+T-120.00
+P7-Eleven
+LFood:Lunch
+C*
+^
+D03/12/2022
+T500.00
+L[Bank]
+^
+!Account
+NBank
+TBank
+^
+!Type:Bank
+D03/12/2022
+T-800.00
+MShopping
+SFood:Lunch
+$-300.00
+S[Cash]
+EWithdraw
+$-500.00
+^
+";
+
+    let data = import_qif(qif, &options())?;
+
+    let accounts = data
+        .accounts
+        .iter()
+        .map(|a| (a.title.as_str(), a.r#type.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(accounts, vec![("Cash", "CASH"), ("Bank", "BANK")]);
+    assert_eq!(data.categories.len(), 3);
+    assert_eq!(data.transactions[0].status.as_deref(), Some("CL"));
+    assert_eq!(data.transactions[0].category_id, 2);
+
+    // the transfer listed in both accounts is only kept as the split line
+    let transfers = data
+        .transactions
+        .iter()
+        .filter(|t| t.to_account_id != 0)
+        .collect::<Vec<_>>();
+    assert_eq!(transfers.len(), 1);
+    let parent = data
+        .transactions
+        .iter()
+        .find(|t| t.category_id == SPLIT_CATEGORY_ID)
+        .unwrap();
+    assert_eq!(transfers[0].parent_id, parent.id);
+    assert_eq!(transfers[0].note.as_deref(), Some("Withdraw"));
+    assert_eq!(
+        (transfers[0].from_account_id, transfers[0].to_account_id),
+        (2, 1)
+    );
+
+    let statements = migrate(&data)?;
+    assert_eq!(verify_sql(&statements)?, vec![]);
+    assert_eq!(
+        Report::new(&data, &MigrateOptions::default(), &statements)?
+            .mismatches()
+            .count(),
+        0
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reject_malformed_rows() {
+    let csv = "date,account,amount\n2022-13-45,Cash,1.00\n";
+    let error = import_csv(csv.as_bytes(), &options()).err().unwrap();
+    assert!(error.to_string().starts_with("line 2:"));
+
+    assert!(import_csv("account,amount\n".as_bytes(), &options()).is_err());
+}
+
+#[test]
+fn reject_malformed_amounts() {
+    for amount in [
+        "abc",
+        "\"1 234,50\"",
+        "\"1.234,50\"",
+        "1.234",
+        "1.€",
+        "\"12,34\"",
+        "1-",
+        "99999999999999999999",
+    ] {
+        let csv = format!("date,account,amount\n2022-12-01,Cash,{}\n", amount);
+
+        let error = import_csv(csv.as_bytes(), &options()).err();
+
+        assert!(
+            error.is_some_and(|e| e.to_string().contains("invalid amount")),
+            "{} is accepted",
+            amount
+        );
+    }
+}
+
+#[test]
+fn import_amounts() -> Result<()> {
+    let csv = "date,account,amount\n\
+        2022-12-01,Cash,\"-1,234,567.5\"\n\
+        2022-12-02,Cash,+.05\n\
+        2022-12-03,Cash,7\n";
+
+    let data = import_csv(csv.as_bytes(), &options())?;
+
+    let amounts = data
+        .transactions
+        .iter()
+        .map(|t| t.from_amount)
+        .collect::<Vec<_>>();
+    assert_eq!(amounts, vec![-123456750, 5, 700]);
+
+    Ok(())
+}
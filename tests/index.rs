@@ -0,0 +1,32 @@
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+#[test]
+fn look_up_by_id_and_parent() {
+    let parent = transaction(1, 1, 0, SPLIT_CATEGORY_ID, -300);
+    let mut second = transaction(3, 1, 0, 2, -200);
+    second.parent_id = 1;
+    let mut first = transaction(2, 1, 0, 2, -100);
+    first.parent_id = 1;
+    let data = financisto(vec![parent, second, first, transaction(4, 2, 0, 3, 500)]);
+
+    let index = data.index();
+
+    assert_eq!(index.account(2).unwrap().title, "Bank");
+    assert_eq!(index.category(2).unwrap().title, "Lunch");
+    assert_eq!(index.currency(1).unwrap().name, "TWD");
+    assert_eq!(index.payee(1).unwrap().title, "7-Eleven");
+    assert!(index.account(3).is_none());
+    assert_eq!(index.transaction(4).unwrap().from_amount, 500);
+    // in backup order
+    let lines = index
+        .split_lines(1)
+        .iter()
+        .map(|t| t.id)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![3, 2]);
+    assert!(index.split_lines(4).is_empty());
+}
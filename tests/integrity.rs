@@ -0,0 +1,177 @@
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+fn broken() -> Financisto {
+    let mut orphan = transaction(1, 1, 0, 1, -100);
+    orphan.parent_id = 99;
+    let mut unknown = transaction(2, 3, 0, 7, -200);
+    unknown.payee_id = 5;
+    let own = transaction(3, 1, 1, 0, -300);
+    let fine = transaction(4, 1, 0, 1, -400);
+    financisto(vec![orphan, unknown, own, fine])
+}
+
+fn rows(statements: &[String]) -> Vec<&String> {
+    statements
+        .iter()
+        .filter(|s| s.starts_with("INSERT INTO \"TRANSACTIONSTABLE\""))
+        .collect()
+}
+
+#[test]
+fn dangling_references_are_reported() {
+    let issues = check_integrity(&broken());
+
+    assert_eq!(
+        issues,
+        vec![
+            IntegrityIssue::OrphanSplit {
+                transaction_id: 1,
+                parent_id: 99,
+            },
+            IntegrityIssue::UnknownAccount {
+                transaction_id: 2,
+                account_id: 3,
+            },
+            IntegrityIssue::UnknownCategory {
+                transaction_id: 2,
+                category_id: 7,
+            },
+            IntegrityIssue::UnknownPayee {
+                transaction_id: 2,
+                payee_id: 5,
+            },
+            IntegrityIssue::SelfTransfer {
+                transaction_id: 3,
+                account_id: 1,
+            },
+        ]
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "split line 1 refers to parent 99 that is not migrated"
+    );
+    assert!(check_integrity(&financisto(vec![])).is_empty());
+}
+
+#[test]
+fn repair_includes_orphans_and_skips_broken_transactions() -> Result<()> {
+    let options = MigrateOptions {
+        integrity: Integrity::Repair,
+        ..Default::default()
+    };
+
+    let statements = migrate_transactions(&broken(), &options)?;
+    let rows = rows(&statements);
+
+    // the orphan split line and the fine transaction
+    assert_eq!(rows.len(), 2);
+    assert_eq!(value_of(rows[0], 2), "-1000000");
+    assert_eq!(value_of(rows[1], 2), "-4000000");
+
+    Ok(())
+}
+
+#[test]
+fn repair_moves_unknown_categories_to_uncategorized() -> Result<()> {
+    let data = financisto(vec![transaction(1, 1, 0, 7, -100)]);
+    let options = MigrateOptions {
+        integrity: Integrity::Repair,
+        ..Default::default()
+    };
+    let ids = CategoryIds::new(&data.categories);
+
+    let statements = migrate_transactions(&data, &options)?;
+
+    assert_eq!(
+        value_of(rows(&statements)[0], 7),
+        ids.uncategorized_expense.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn strict_refuses_broken_data() {
+    let options = MigrateOptions {
+        integrity: Integrity::Strict,
+        ..Default::default()
+    };
+
+    let error = migrate_transactions(&broken(), &options).err().unwrap();
+
+    assert_eq!(error.to_string().lines().count(), 5);
+}
+
+#[test]
+fn split_lines_under_unscheduled_templates_are_orphans() -> Result<()> {
+    let mut template = transaction(1, 1, 0, 1, -100);
+    template.is_template = 1;
+    let mut line = transaction(2, 1, 0, 1, -100);
+    line.parent_id = 1;
+    line.is_template = 1;
+    let mut scheduled = transaction(3, 1, 0, 1, -200);
+    scheduled.is_template = 2;
+    let mut scheduled_line = transaction(4, 1, 0, 1, -200);
+    scheduled_line.parent_id = 3;
+    let data = financisto(vec![template, line, scheduled, scheduled_line]);
+
+    assert_eq!(
+        check_integrity(&data),
+        vec![IntegrityIssue::OrphanSplit {
+            transaction_id: 2,
+            parent_id: 1,
+        }]
+    );
+
+    let repair = MigrateOptions {
+        integrity: Integrity::Repair,
+        ..Default::default()
+    };
+    let statements = migrate_transactions(&data, &repair)?;
+    assert!(rows(&statements)
+        .iter()
+        .any(|r| value_of(r, 2) == "-1000000"));
+
+    let strict = MigrateOptions {
+        integrity: Integrity::Strict,
+        ..Default::default()
+    };
+    assert!(migrate_transactions(&data, &strict).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn duplicate_ids() -> Result<()> {
+    let mut data = financisto(vec![transaction(1, 1, 0, 1, -100)]);
+    data.accounts.push(account(2, "Savings", "BANK"));
+
+    assert_eq!(
+        check_integrity(&data),
+        vec![IntegrityIssue::DuplicateId {
+            entity: "account",
+            id: 2,
+        }]
+    );
+    let error = migrate_accounts(&data, &MigrateOptions::default())
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "duplicate account ID 2");
+
+    let repair = MigrateOptions {
+        integrity: Integrity::Repair,
+        ..Default::default()
+    };
+    let accounts = migrate_accounts(&data, &repair)?
+        .into_iter()
+        .filter(|s| s.starts_with("INSERT INTO \"ACCOUNTSTABLE\""))
+        .collect::<Vec<_>>();
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(value_of(&accounts[1], 1), "Bank");
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "serialize")]
+
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+fn book() -> Financisto {
+    let mut book = BackupBuilder::new();
+    let twd = book.currency("TWD");
+    let cash = book.account("Cash", "CASH", twd);
+    let bank = book.account("Bank", "BANK", twd);
+    let lunch = book.category("Food:Lunch");
+    book.payee("7-Eleven");
+    book.transaction(cash, lunch, -12000).note = Some("rice\nand tea".to_owned());
+    book.transfer(bank, cash, 300000);
+    book.build()
+}
+
+#[test]
+fn json_round_trip() -> Result<()> {
+    let mut data = book();
+    let mut budget = std::collections::HashMap::new();
+    budget.insert("entity".to_owned(), "budget".to_owned());
+    budget.insert("_id".to_owned(), "1".to_owned());
+    data.raw.push(budget.into());
+
+    let loaded = Financisto::from_json(&data.to_json()?)?;
+
+    assert_eq!(
+        format!("{:?}", loaded.accounts),
+        format!("{:?}", data.accounts)
+    );
+    assert_eq!(
+        format!("{:?}", loaded.categories),
+        format!("{:?}", data.categories)
+    );
+    assert_eq!(
+        format!("{:?}", loaded.transactions),
+        format!("{:?}", data.transactions)
+    );
+    assert_eq!(loaded.raw.get("budget", 1), data.raw.get("budget", 1));
+
+    Ok(())
+}
+
+#[test]
+fn edited_json_migrates() -> Result<()> {
+    let json = book().to_json()?.replace("\"Cash\"", "\"Wallet\"");
+    let path = std::env::temp_dir().join(format!("financisto-{}.json", std::process::id()));
+    std::fs::write(&path, json)?;
+
+    let data = read_json(&path);
+    std::fs::remove_file(&path)?;
+    let statements = migrate_accounts(&data?, &MigrateOptions::default())?;
+
+    assert!(statements.iter().any(|s| s.contains("'Wallet'")));
+
+    Ok(())
+}
+
+#[test]
+fn hand_written_json() -> Result<()> {
+    // kinds of entities and optional fields left out
+    let data = Financisto::from_json(
+        r#"{
+            "currencies": [{
+                "id": 1, "updated_on": 0, "title": "TWD", "entity": "currency", "decimals": 2,
+                "symbol": "$", "is_active": 1, "name": "TWD", "is_default": 1, "symbol_format": "RS"
+            }]
+        }"#,
+    )?;
+
+    assert_eq!(data.currencies[0].decimal_separator, None);
+    assert!(data.accounts.is_empty());
+    assert!(data.raw.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,306 @@
+use financisto2bluecoins::*;
+
+fn lines(text: &str) -> Vec<String> {
+    text.split('\n').map(|s| s.to_owned()).collect()
+}
+
+const HEADER: &str = "PACKAGE:ru.orangesoft.financisto
+VERSION_CODE:117
+VERSION_NAME:1.8.4
+DATABASE_VERSION:218
+#START";
+
+const TRANSACTION: &str = "$ENTITY:transactions
+_id:7
+from_account_id:1
+to_account_id:0
+category_id:2
+project_id:0
+location_id:0
+payee_id:0
+parent_id:0
+note:dinner
+from_amount:-1500
+to_amount:0
+original_currency_id:0
+original_from_amount:0
+datetime:1669900000000
+provider:network
+accuracy:0.0
+latitude:0.0
+longitude:0.0
+is_template:0
+status:RC
+is_ccard_payment:0
+last_recurrence:0
+updated_on:1669900000000
+$$";
+
+#[test]
+fn parse_transaction() -> Result<()> {
+    let data = convert_maps(&lines(&format!("{}\n{}\n#END", HEADER, TRANSACTION)))?;
+
+    assert_eq!(data.transactions.len(), 1);
+    let tx = &data.transactions[0];
+    assert_eq!(tx.id, 7);
+    assert_eq!(tx.note.as_deref(), Some("dinner"));
+    assert_eq!(tx.from_amount, -1500);
+    assert_eq!(tx.status.as_deref(), Some("RC"));
+
+    Ok(())
+}
+
+const PROJECT: &str = "$ENTITY:project
+_id:1
+title:Trip
+is_active:1
+updated_on:1669900000000
+$$";
+
+const LOCATION: &str = "$ENTITY:locations
+_id:2
+name:Taipei 101
+datetime:1669900000000
+provider:gps
+accuracy:5.0
+latitude:25.0339
+longitude:121.5645
+is_payee:0
+count:3
+updated_on:1669900000000
+$$";
+
+#[test]
+fn parse_projects_and_locations() -> Result<()> {
+    let data = convert_maps(&lines(&format!(
+        "{}\n{}\n{}\n#END",
+        HEADER, PROJECT, LOCATION
+    )))?;
+
+    assert_eq!(data.projects[0].title, "Trip");
+    assert_eq!(data.locations[0].id, 2);
+    assert_eq!(data.locations[0].name, "Taipei 101");
+    assert_eq!(data.locations[0].resolved_address, None);
+    assert_eq!(data.locations[0].count, 3);
+
+    Ok(())
+}
+
+/// The transaction with its note replaced.
+fn with_note(note: &str) -> String {
+    TRANSACTION.replace("note:dinner", &format!("note:{}", note))
+}
+
+#[test]
+fn parse_multi_line_notes() -> Result<()> {
+    let notes = [
+        "time: 19:30, table 3",
+        "first line\nsecond line\n\nafter a blank line",
+        "paid $$ in cash",
+        "price list\n$$\ntotal 15.00",
+        "Remember: bring the receipt",
+        "ends with\n$$",
+    ];
+    let text = notes.iter().map(|n| with_note(n)).collect::<Vec<_>>();
+
+    let data = convert_maps(&lines(&format!(
+        "{}\n{}\n{}\n#END",
+        HEADER,
+        text.join("\n"),
+        PROJECT
+    )))?;
+
+    let parsed = data
+        .transactions
+        .iter()
+        .map(|t| t.note.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(parsed, notes);
+    assert!(data.transactions.iter().all(|t| t.from_amount == -1500));
+    assert_eq!(data.projects.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn note_lines_shaped_like_fields() -> Result<()> {
+    let notes = [
+        "shopping\nTodo: call bank\n2 x milk: 40",
+        "refund\ntodo: call bank",
+    ];
+    let text = format!(
+        "{}\n{}\n#END",
+        HEADER,
+        notes
+            .iter()
+            .map(|n| with_note(n))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+
+    for data in [convert_maps(&lines(&text))?, parse_backup(&text)?] {
+        let parsed = data
+            .transactions
+            .iter()
+            .map(|t| t.note.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        // a lower case name starts a field of its own
+        assert_eq!(parsed, [notes[0], "refund"]);
+        assert!(data.raw.fields("transactions").contains("todo"));
+        assert!(data.transactions.iter().all(|t| t.from_amount == -1500));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_unknown_columns() -> Result<()> {
+    let currency = "$ENTITY:currency
+_id:1
+name:TWD
+title:TWD
+symbol:$
+is_default:1
+decimals:2
+symbol_format:RS
+is_active:1
+updated_on:0
+rounding:HALF_UP
+$$";
+    let text = format!(
+        "{}\n{}\n{}\n#END",
+        HEADER,
+        currency,
+        TRANSACTION.replace("status:RC", "status:RC\nlabels:trip")
+    );
+
+    for data in [convert_maps(&lines(&text))?, parse_backup(&text)?] {
+        assert_eq!(data.currencies[0].updated_on, 0);
+        assert!(data.raw.fields("currency").contains("rounding"));
+        let tx = &data.transactions[0];
+        assert_eq!(tx.status.as_deref(), Some("RC"));
+        assert!(data.raw.fields("transactions").contains("labels"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_unterminated_entities() -> Result<()> {
+    let truncated = TRANSACTION.trim_end_matches("$$").trim_end();
+    let data = convert_maps(&lines(&format!(
+        "{}\n{}\n{}",
+        HEADER,
+        PROJECT.trim_end_matches("$$").trim_end(),
+        truncated
+    )))?;
+
+    assert_eq!(data.projects.len(), 1);
+    assert_eq!(data.transactions.len(), 1);
+
+    Ok(())
+}
+
+const BUDGET: &str = "$ENTITY:budget
+_id:3
+title:Food
+amount:300000
+category_id:1
+$$";
+
+#[test]
+fn keep_raw_entities() -> Result<()> {
+    let data = convert_maps(&lines(&format!(
+        "{}\n{}\n{}\n{}\n#END",
+        HEADER, PROJECT, BUDGET, TRANSACTION
+    )))?;
+
+    // only the kind that is not modeled is kept, the others are counted
+    let raw = &data.raw;
+    assert_eq!(raw.len(), 1);
+    assert_eq!(
+        raw.types().collect::<Vec<_>>(),
+        vec!["budget", "project", "transactions"]
+    );
+    assert_eq!(raw.count("transactions"), 1);
+    let budget = raw.get("budget", 3).unwrap();
+    assert_eq!(budget.entity(), "budget");
+    assert_eq!(budget.get("amount"), Some("300000"));
+    assert!(raw.get("budget", 7).is_none());
+    assert!(raw.get("transactions", 7).is_none());
+    assert!(raw.fields("transactions").contains("note"));
+    assert_eq!(
+        raw.fields("budget").into_iter().collect::<Vec<_>>(),
+        vec!["_id", "amount", "category_id", "entity", "title"]
+    );
+    assert!(raw.of_type("attributes").is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn parse_without_maps() -> Result<()> {
+    let notes = ["a: b", "two\nlines", "$$ inside", "dollars\n$$\nafter"];
+    let mut entities = vec![PROJECT.to_owned(), LOCATION.to_owned(), BUDGET.to_owned()];
+    entities.extend(notes.iter().map(|n| with_note(n)));
+    let text = format!("{}\n{}\n#END\n", HEADER, entities.join("\n"));
+
+    let maps = convert_maps(&lines(&text))?;
+    let parsed = parse_backup(&text)?;
+
+    assert_eq!(parsed, maps);
+    assert_eq!(
+        parsed.raw.types().collect::<Vec<_>>(),
+        vec!["budget", "locations", "project", "transactions"]
+    );
+    assert_eq!(parsed.raw.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn parse_header_like_maps() -> Result<()> {
+    // the 4 header lines are skipped whatever they hold, with or without `#START`
+    for header in [
+        HEADER.to_owned(),
+        format!("{}\n{}", HEADER, HEADER),
+        "\n\n\n".to_owned(),
+    ] {
+        let text = format!("{}\n{}\n#END", header, PROJECT);
+
+        assert_eq!(parse_backup(&text)?, convert_maps(&lines(&text))?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn report_malformed_fields() {
+    let missing = TRANSACTION.replace("from_amount:-1500\n", "");
+    let error = parse_backup(&format!("{}\n{}\n#END", HEADER, missing))
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "transactions 7: missing field from_amount"
+    );
+
+    let invalid = TRANSACTION.replace("from_amount:-1500", "from_amount:-15.00");
+    let error = parse_backup(&format!("{}\n{}\n#END", HEADER, invalid))
+        .err()
+        .unwrap();
+    assert!(error
+        .to_string()
+        .starts_with("transactions 7: invalid from_amount '-15.00'"));
+}
+
+#[test]
+fn convert_malformed_map() {
+    let mut map = std::collections::HashMap::new();
+    map.insert("entity".to_owned(), "payee".to_owned());
+    map.insert("_id".to_owned(), "x".to_owned());
+
+    let error = FinancistoPayee::try_from(&map).err().unwrap();
+
+    assert!(error.to_string().starts_with("payee x: invalid _id 'x'"));
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use financisto2bluecoins::*;
+use proptest::prelude::*;
+
+mod common;
+
+use common::*;
+
+const HEADER: &str =
+    "PACKAGE:ru.orangesoft.financisto\nVERSION_CODE:117\nVERSION_NAME:1.8.4\nDATABASE_VERSION:218\n#START\n";
+
+/// Start of the built transactions, 2022-12-01 09:00 in Taipei.
+const START: i64 = 1669856400000;
+
+fn options(integrity: Integrity) -> MigrateOptions {
+    MigrateOptions {
+        integrity,
+        timezone: "Asia/Taipei".parse().unwrap(),
+        ..Default::default()
+    }
+}
+
+fn migrate(data: &Financisto, options: &MigrateOptions) -> Result<Vec<String>> {
+    let mut statements = migrate_accounts(data, options)?;
+    statements.append(&mut migrate_categories(data, options)?);
+    statements.append(&mut migrate_transactions(data, options)?);
+    Ok(statements)
+}
+
+/// Lines that look like a backup: entity boundaries, fields of the models and junk.
+fn backup_line() -> impl Strategy<Value = String> {
+    let name = prop::sample::select(vec![
+        "_id",
+        "entity",
+        "title",
+        "name",
+        "left",
+        "right",
+        "currency_id",
+        "from_account_id",
+        "to_account_id",
+        "category_id",
+        "parent_id",
+        "from_amount",
+        "to_amount",
+        "datetime",
+        "note",
+        "is_template",
+    ]);
+    let value = prop_oneof!["-?[0-9]{1,4}", "[a-zA-Z :$]{0,8}"];
+
+    prop_oneof![
+        Just("$$".to_owned()),
+        Just("#END".to_owned()),
+        "\\$ENTITY:(currency|account|category|payee|project|locations|transactions|budget)",
+        (name, value).prop_map(|(name, value)| format!("{}:{}", name, value)),
+        ".{0,12}",
+    ]
+}
+
+/// A valid book of one currency, built from random accounts, categories and transactions.
+#[derive(Debug, Clone)]
+enum Op {
+    Transaction {
+        account: usize,
+        category: Option<usize>,
+        amount: i64,
+    },
+    Transfer {
+        from: usize,
+        to: usize,
+        amount: i64,
+    },
+    Split {
+        account: usize,
+        lines: Vec<(Line, i64)>,
+    },
+    Schedule {
+        account: usize,
+        category: Option<usize>,
+        amount: i64,
+    },
+}
+
+/// Split line of a category or transferring to an account.
+#[derive(Debug, Clone)]
+enum Line {
+    Category(usize),
+    Transfer(usize),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let amount = -1_000_000_000i64..1_000_000_000;
+    prop_oneof![
+        (any::<usize>(), any::<Option<usize>>(), amount.clone()).prop_map(
+            |(account, category, amount)| Op::Transaction {
+                account,
+                category,
+                amount
+            }
+        ),
+        (any::<usize>(), any::<usize>(), 1i64..1_000_000_000)
+            .prop_map(|(from, to, amount)| Op::Transfer { from, to, amount }),
+        (
+            any::<usize>(),
+            prop::collection::vec(
+                (
+                    prop_oneof![
+                        any::<usize>().prop_map(Line::Category),
+                        any::<usize>().prop_map(Line::Transfer)
+                    ],
+                    amount.clone()
+                ),
+                1..5
+            )
+        )
+            .prop_map(|(account, lines)| Op::Split { account, lines }),
+        (any::<usize>(), any::<Option<usize>>(), amount).prop_map(|(account, category, amount)| {
+            Op::Schedule {
+                account,
+                category,
+                amount,
+            }
+        }),
+    ]
+}
+
+/// Accounts, category paths and transactions, each happening at a minute from [`START`] so that
+/// several share the same time.
+fn book() -> impl Strategy<Value = Financisto> {
+    (
+        1usize..5,
+        prop::collection::vec("[A-C](:[a-c]){0,2}", 0..6),
+        prop::collection::vec((op(), 0i64..60), 0..30),
+    )
+        .prop_map(|(accounts, paths, ops)| {
+            let mut book = BackupBuilder::new();
+            let twd = book.currency("TWD");
+            let accounts = (0..accounts)
+                .map(|i| book.account(&format!("Account {}", i), "BANK", twd))
+                .collect::<Vec<_>>();
+            let categories = paths.iter().map(|p| book.category(p)).collect::<Vec<_>>();
+            let account = |i: usize| accounts[i % accounts.len()];
+            let category = |i: Option<usize>| match i {
+                Some(i) if !categories.is_empty() => categories[i % categories.len()],
+                _ => NO_CATEGORY_ID,
+            };
+            // another account than `from`, if there is one
+            let other = |from: i32, i: usize| {
+                let others = accounts.iter().filter(|a| **a != from).collect::<Vec<_>>();
+                others.get(i % others.len().max(1)).map(|a| **a)
+            };
+
+            for (op, minute) in ops {
+                let datetime = START + minute * 60000;
+                match op {
+                    Op::Transaction {
+                        account: a,
+                        category: c,
+                        amount,
+                    } => book.transaction(account(a), category(c), amount).datetime = datetime,
+                    Op::Transfer { from, to, amount } => {
+                        let from = account(from);
+                        if let Some(to) = other(from, to) {
+                            book.transfer(from, to, amount).datetime = datetime;
+                        }
+                    }
+                    Op::Split { account: a, lines } => {
+                        let from = account(a);
+                        let split = book.split(from);
+                        split.datetime = datetime;
+                        let split = split.id;
+                        for (target, amount) in lines {
+                            match target {
+                                Line::Category(c) => {
+                                    book.split_line(split, category(Some(c)), amount);
+                                }
+                                Line::Transfer(to) => {
+                                    if let Some(to) = other(from, to) {
+                                        let line =
+                                            book.split_line(split, NO_CATEGORY_ID, -amount.abs());
+                                        line.to_account_id = to;
+                                        line.to_amount = amount.abs();
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Op::Schedule {
+                        account: a,
+                        category: c,
+                        amount,
+                    } => {
+                        book.schedule(
+                            account(a),
+                            category(c),
+                            amount,
+                            "2022-12-05T09:00:00~MONTHLY:interval@1#~EXACTLY_TIMES:12",
+                        )
+                        .datetime = datetime;
+                    }
+                }
+            }
+
+            book.build()
+        })
+}
+
+/// A valid backup with lines removed, duplicated or given another value.
+fn damaged_backup() -> impl Strategy<Value = String> {
+    let mut book = BackupBuilder::new();
+    let twd = book.currency("TWD");
+    let cash = book.account("Cash", "CASH", twd);
+    let bank = book.account("Bank", "BANK", twd);
+    let lunch = book.category("Food:Lunch");
+    let salary = book.category("Salary");
+    book.payee("7-Eleven");
+    book.transaction(bank, salary, 5000000);
+    book.transfer(bank, cash, 300000);
+    book.transaction(cash, lunch, -12000);
+    let split = book.split(bank).id;
+    book.split_line(split, lunch, -8000);
+    let line = book.split_line(split, NO_CATEGORY_ID, -10000);
+    line.to_account_id = cash;
+    line.to_amount = 10000;
+    let text = backup_text(&book.build());
+
+    let lines = text.lines().map(|l| l.to_owned()).collect::<Vec<_>>();
+    let value = prop_oneof![
+        "-?[0-9]{1,3}",
+        "-?[0-9]{16,19}",
+        Just("2147483647".to_owned()),
+        Just("".to_owned()),
+        Just("x".to_owned())
+    ];
+    prop::collection::vec((any::<prop::sample::Index>(), 0..3u8, value), 1..8).prop_map(
+        move |damages| {
+            let mut lines = lines.clone();
+            for (index, damage, value) in damages {
+                let index = index.index(lines.len());
+                match damage {
+                    0 => {
+                        lines.remove(index);
+                    }
+                    1 => {
+                        let line = lines[index].clone();
+                        lines.insert(index, line);
+                    }
+                    _ => {
+                        if let Some((name, _)) = lines[index].split_once(':') {
+                            lines[index] = format!("{}:{}", name, value);
+                        }
+                    }
+                }
+            }
+            lines.join("\n")
+        },
+    )
+}
+
+proptest! {
+    /// Both parsers take any text without panicking, and agree on what it holds.
+    #[test]
+    fn parse_any_text(
+        header in any::<bool>(),
+        body in prop::collection::vec(backup_line(), 0..40),
+    ) {
+        let text = format!("{}{}", if header { HEADER } else { "" }, body.join("\n"));
+        let lines = text.split('\n').map(|l| l.to_owned()).collect::<Vec<_>>();
+
+        match (convert_maps(&lines), parse_backup(&text)) {
+            (Ok(maps), Ok(parsed)) => prop_assert_eq!(maps, parsed),
+            (Err(a), Err(b)) => prop_assert_eq!(a.to_string(), b.to_string()),
+            (a, b) => prop_assert!(false, "convert_maps {:?}, parse_backup {:?}", a.err(), b.err()),
+        }
+    }
+
+    /// Migrating a damaged backup may fail, but never panics, and repairing it leaves unique IDs and
+    /// paired transfers.
+    #[test]
+    fn migrate_damaged_backup(text in damaged_backup()) {
+        let Ok(data) = parse_backup(&text) else {
+            return Ok(());
+        };
+
+        let _ = migrate(&data, &options(Integrity::Report));
+        let _ = migrate(&data, &options(Integrity::Strict));
+        if let Ok(statements) = migrate(&data, &options(Integrity::Repair)) {
+            // split lines moved to another account or time are not repaired
+            let violations = verify_sql(&statements)
+                .unwrap()
+                .into_iter()
+                .filter(|v| !matches!(v, Violation::SplitGroup { .. }))
+                .collect::<Vec<_>>();
+            prop_assert_eq!(violations, vec![]);
+        }
+    }
+
+    /// Any valid book migrates into unique IDs, paired and balanced transfers, and the balances of
+    /// Financisto.
+    #[test]
+    fn migrate_any_book(data in book()) {
+        let statements = migrate(&data, &options(Integrity::Strict)).unwrap();
+
+        prop_assert_eq!(verify_sql(&statements).unwrap(), vec![]);
+        let report = Report::new(&data, &options(Integrity::Strict), &statements).unwrap();
+        prop_assert_eq!(report.mismatches().count(), 0);
+
+        let mut transfers: HashMap<i64, i64> = HashMap::new();
+        for row in statements.iter().map(|s| Insert::parse(s).unwrap()) {
+            if row.table == "TRANSACTIONSTABLE" && row.int("transactionTypeID") == TYPE_TRANSFER as i64 {
+                *transfers.entry(row.int("transferGroupID")).or_default() += row.int("amount");
+            }
+        }
+        prop_assert!(
+            transfers.values().all(|sum| *sum == 0),
+            "unbalanced transfers {:?}",
+            transfers
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use chrono::NaiveDate;
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+fn book() -> Financisto {
+    let parent = transaction(1, 1, 0, SPLIT_CATEGORY_ID, -300);
+    let mut line = transaction(2, 1, 0, 2, -500);
+    line.parent_id = 1;
+    let mut refund = transaction(3, 1, 0, 1, 200);
+    refund.parent_id = 1;
+    financisto(vec![
+        parent,
+        line,
+        refund,
+        transaction(4, 2, 0, 3, 10000),
+        transaction(5, 2, 1, 0, -1000),
+    ])
+}
+
+fn migrate(data: &Financisto) -> Result<Vec<String>> {
+    migrate_with(data, &MigrateOptions::default())
+}
+
+fn migrate_with(data: &Financisto, options: &MigrateOptions) -> Result<Vec<String>> {
+    let mut statements = migrate_accounts(data, options)?;
+    statements.append(&mut migrate_categories(data, options)?);
+    statements.append(&mut migrate_transactions(data, options)?);
+    Ok(statements)
+}
+
+#[test]
+fn parse_generated_statements() -> Result<()> {
+    let statement = BluecoinsTransaction {
+        transactions_table_id: 1,
+        notes: "it's 'quoted', really".to_owned(),
+        ..Default::default()
+    }
+    .to_sql();
+
+    let row = Insert::parse(&statement)?;
+
+    assert_eq!(row.table, "TRANSACTIONSTABLE");
+    assert_eq!(row.get("notes"), Some("it's 'quoted', really"));
+    assert_eq!(row.int("transactionsTableID"), 1);
+    assert_eq!(row.get("reminderTransaction"), None);
+    assert!(Insert::parse("DELETE FROM \"TRANSACTIONSTABLE\";").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn balances_match() -> Result<()> {
+    let data = book();
+
+    let report = Report::new(&data, &MigrateOptions::default(), &migrate(&data)?)?;
+
+    assert_eq!(report.financisto["transactions"], 5);
+    assert_eq!(report.bluecoins["ACCOUNTSTABLE"], 2);
+    assert_eq!(report.bluecoins["TRANSACTIONSTABLE"], 2 + 2 + 1 + 2);
+    assert_eq!(report.mismatches().count(), 0);
+
+    let cash = &report.accounts[0];
+    assert_eq!(cash.title, "Cash");
+    assert_eq!(
+        cash.financisto,
+        Totals {
+            opening: 0,
+            income: 200,
+            expense: -500,
+            transfer_in: 1000,
+            transfer_out: 0,
+            balance: 700,
+        }
+    );
+    let bank = &report.accounts[1];
+    assert_eq!(bank.bluecoins.transfer_out, -1000);
+    assert_eq!(bank.bluecoins.balance, 9000);
+
+    assert!(report.to_string().ends_with("all accounts match"));
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()?)?;
+    assert_eq!(json["accounts"][1]["bluecoins"]["income"], 10000);
+
+    Ok(())
+}
+
+#[test]
+fn missing_rows_are_mismatches() -> Result<()> {
+    let data = book();
+    let mut statements = migrate(&data)?;
+    statements.pop();
+
+    let report = Report::new(&data, &MigrateOptions::default(), &statements)?;
+
+    let mismatches = report.mismatches().collect::<Vec<_>>();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].title, "Cash");
+    assert!(report.to_string().contains("!! Cash"));
+    assert_eq!(report.to_json()?.matches("\"mismatch\": true").count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn filtered_migration_matches() -> Result<()> {
+    let mut data = book();
+    // 2022-11-15, before the migrated range
+    let mut lunch = transaction(6, 1, 0, 2, -700);
+    lunch.datetime = 1668470400000;
+    let mut withdraw = transaction(7, 2, 1, 0, -2000);
+    withdraw.datetime = 1668470400000;
+    let mut orphan = transaction(8, 1, 0, 2, -50);
+    orphan.parent_id = 99;
+    data.transactions.extend([lunch, withdraw, orphan]);
+    let options = MigrateOptions {
+        integrity: Integrity::Repair,
+        timezone: "Asia/Taipei".parse()?,
+        filter: Filter {
+            from: NaiveDate::from_ymd_opt(2022, 12, 1),
+            accounts: Selection {
+                include: vec!["Cash".to_owned()],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let report = Report::new(&data, &options, &migrate_with(&data, &options)?)?;
+
+    assert_eq!(report.mismatches().count(), 0);
+    assert_eq!(report.accounts.len(), 1);
+    assert_eq!(
+        report.accounts[0].financisto,
+        Totals {
+            opening: 1300,
+            // the refund, and the transfer from the bank that is left out
+            income: 1200,
+            expense: -550,
+            transfer_in: 0,
+            transfer_out: 0,
+            balance: 1950,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dropped_transactions_are_mismatches() -> Result<()> {
+    let mut data = book();
+    // a line of a split that is not in the backup is left out without repairs
+    let mut orphan = transaction(7, 1, 0, 2, -50);
+    orphan.parent_id = 99;
+    data.transactions
+        .extend([transaction(6, 9, 0, 2, -100), orphan]);
+    let data = with_balances(data);
+
+    let report = Report::new(&data, &MigrateOptions::default(), &migrate(&data)?)?;
+
+    // the account that is not in the backup is migrated all the same
+    let mismatches = report.mismatches().map(|a| a.id).collect::<Vec<_>>();
+    assert_eq!(mismatches, vec![1]);
+    assert_eq!(report.accounts[2].bluecoins.expense, -100);
+    assert!(report.to_string().ends_with("1 account(s) do not match"));
+
+    Ok(())
+}
+
+#[test]
+fn stored_balances_are_checked() -> Result<()> {
+    let mut data = book();
+    data.accounts[0].total_amount += 100;
+    let statements = migrate(&data)?;
+
+    let report = Report::new(&data, &MigrateOptions::default(), &statements)?;
+
+    let mismatches = report.mismatches().collect::<Vec<_>>();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].stored_balance, Some(800));
+    assert_eq!(mismatches[0].financisto, mismatches[0].bluecoins);
+    assert!(report
+        .to_string()
+        .contains("!! Cash                 stored"));
+
+    // a filtered migration does not add up to the stored balance
+    let options = MigrateOptions {
+        filter: Filter {
+            from: NaiveDate::from_ymd_opt(2022, 12, 1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let report = Report::new(&data, &options, &migrate_with(&data, &options)?)?;
+    assert_eq!(report.mismatches().count(), 0);
+    assert_eq!(report.accounts[0].stored_balance, None);
+
+    Ok(())
+}
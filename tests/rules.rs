@@ -0,0 +1,165 @@
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+const RULES: &str = r#"
+[[rule]]
+match = { note = "lunch", type = "expense" }
+set = { category = "Food:Lunch", item = "Lunch" }
+
+[[rule]]
+match = { payee = "7-Eleven", max_amount = 100 }
+set = { labels = ["convenience"] }
+
+[[rule]]
+match = { account = "Bank", type = "income", min_amount = 1000 }
+set = { category = "收入", labels = ["salary"] }
+"#;
+
+#[test]
+fn load_rules_from_toml_and_json() -> Result<()> {
+    let rules = Rules::from_toml(RULES)?;
+    assert_eq!(rules.rules.len(), 3);
+    assert_eq!(rules.rules[0].condition.note.as_deref(), Some("lunch"));
+    assert_eq!(
+        rules.rules[0].condition.r#type,
+        Some(TransactionKind::Expense)
+    );
+
+    let rules = Rules::from_json(
+        r#"{ "rule": [ { "match": { "payee": "7-Eleven" }, "set": { "labels": ["a", "b"] } } ] }"#,
+    )?;
+    assert_eq!(rules.rules[0].set.labels, vec!["a", "b"]);
+
+    assert!(Rules::from_toml("[[rule]]\nmatch = { unknown = 1 }").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn apply_matching_rules() -> Result<()> {
+    let mut lunch = transaction(1, 1, 0, 0, -12000);
+    lunch.note = Some("Team LUNCH".to_owned());
+    let mut snack = transaction(2, 1, 0, 1, -5000);
+    snack.payee_id = 1;
+    let salary = transaction(3, 2, 0, 0, 5000000);
+    let small = transaction(4, 2, 0, 0, 50000);
+    let data = financisto(vec![]);
+
+    let rules = Rules::from_toml(RULES)?;
+    let rules = rules.resolve(&data)?;
+
+    assert_eq!(
+        rules.apply(&lunch),
+        RuleOutcome {
+            category_id: Some(2),
+            item: Some("Lunch".to_owned()),
+            notes: None,
+            labels: vec![],
+        }
+    );
+    assert_eq!(rules.apply(&snack).labels, vec!["convenience"]);
+    assert_eq!(rules.apply(&salary).category_id, Some(3));
+    assert_eq!(rules.apply(&salary).labels, vec!["salary"]);
+    assert_eq!(rules.apply(&small), RuleOutcome::default());
+
+    Ok(())
+}
+
+#[test]
+fn unknown_titles_are_rejected() -> Result<()> {
+    let data = financisto(vec![]);
+    let rules = Rules::from_toml("[[rule]]\nset = { category = \"Nope\" }")?;
+
+    let error = rules.resolve(&data).err().unwrap();
+
+    assert_eq!(error.to_string(), "rule 1: unknown category 'Nope'");
+
+    Ok(())
+}
+
+#[test]
+fn rules_applied_during_migration() -> Result<()> {
+    let mut lunch = transaction(1, 1, 0, 0, -12000);
+    lunch.note = Some("lunch with team".to_owned());
+    let mut snack = transaction(2, 1, 0, 1, -5000);
+    snack.payee_id = 1;
+    let data = financisto(vec![lunch, snack]);
+    let options = MigrateOptions {
+        rules: Rules::from_toml(RULES)?,
+        ..Default::default()
+    };
+
+    let statements = migrate_transactions(&data, &options)?;
+
+    // item "Lunch" is created instead of the note, and the category is Lunch (2 + 19)
+    assert!(statements[0].starts_with("INSERT INTO \"ITEMTABLE\""));
+    assert_eq!(value_of(&statements[0], 1), "Lunch");
+    assert_eq!(value_of(&statements[1], 7), "21");
+
+    let label = statements.last().unwrap();
+    assert!(label.starts_with("INSERT INTO \"LABELSTABLE\""));
+    assert_eq!(value_of(label, 1), "convenience");
+    assert_eq!(value_of(label, 2), value_of(&statements[2], 0));
+
+    Ok(())
+}
+
+#[test]
+fn extract_note_into_notes_with_fixed_item() -> Result<()> {
+    let mut ticket = transaction(1, 2, 0, 1, -5000);
+    ticket.note = Some("12345678".to_owned());
+    let mut other = transaction(2, 2, 0, 1, -5000);
+    other.note = Some("dinner".to_owned());
+    let data = financisto(vec![ticket, other]);
+    let options = MigrateOptions {
+        rules: Rules::from_toml(
+            r#"
+            [[rule]]
+            match = { account = "Bank", note_pattern = '^[+-]?(\d+)$' }
+            set = { item = "Lottery", notes = "No. $1" }
+            "#,
+        )?,
+        ..Default::default()
+    };
+
+    let statements = migrate_transactions(&data, &options)?;
+
+    assert_eq!(value_of(&statements[0], 1), "Lottery");
+    assert_eq!(value_of(&statements[1], 1), value_of(&statements[0], 0));
+    assert_eq!(value_of(&statements[1], 9), "No. 12345678");
+    assert_eq!(value_of(&statements[2], 1), "dinner");
+    assert_eq!(value_of(&statements[3], 9), "");
+
+    Ok(())
+}
+
+#[test]
+fn invalid_note_pattern_is_rejected() -> Result<()> {
+    let data = financisto(vec![]);
+    let rules = Rules::from_toml("[[rule]]\nmatch = { note_pattern = \"(\" }")?;
+
+    let error = rules.resolve(&data).err().unwrap();
+
+    assert!(error
+        .to_string()
+        .starts_with("rule 1: invalid note_pattern"));
+
+    Ok(())
+}
+
+#[test]
+fn example_rules_load() -> Result<()> {
+    let rules = Rules::load(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/data/rules.example.toml"
+    ))?;
+
+    let invoice = rules.rules.last().unwrap();
+    assert!(invoice.condition.note_pattern.is_some());
+    assert_eq!(invoice.set.notes.as_deref(), Some("${number}"));
+
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use chrono::NaiveDate;
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+#[test]
+fn parse_recurrence() -> Result<()> {
+    let monthly = Recurrence::parse(
+        "2022-12-01T09:00:00~MONTHLY:interval@1#~EXACTLY_TIMES:12",
+        Timezone::Local,
+    )?;
+    assert_eq!(
+        monthly,
+        Recurrence {
+            start: NaiveDate::from_ymd_opt(2022, 12, 1)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            frequency: Frequency::Monthly,
+            interval: 1,
+            until: Until::Times(12),
+        }
+    );
+
+    // a Thursday, moved to the first Friday
+    let weekly = Recurrence::parse(
+        "2022-12-01T09:00:00~WEEKLY:interval@2#days@FRI#~INDEFINETELY:null",
+        Timezone::Local,
+    )?;
+    assert_eq!(weekly.frequency, Frequency::Weekly);
+    assert_eq!(weekly.interval, 2);
+    assert_eq!(weekly.until, Until::Indefinitely);
+    assert_eq!(
+        weekly.start,
+        NaiveDate::from_ymd_opt(2022, 12, 2)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    );
+    assert!(Recurrence::parse(
+        "2022-12-01T09:00:00~WEEKLY:interval@1#days@MON,FRI#~INDEFINETELY:null",
+        Timezone::Local
+    )
+    .is_err());
+
+    let yearly = Recurrence::parse(
+        "2022-12-01T09:00:00~GEEKY:FREQ=YEARLY;INTERVAL=2#~INDEFINETELY:null",
+        Timezone::Local,
+    )?;
+    assert_eq!(yearly.frequency, Frequency::Yearly);
+    assert_eq!(yearly.interval, 2);
+
+    assert!(Recurrence::parse(
+        "2022-12-01T09:00:00~SEMI_MONTHLY:~INDEFINETELY:null",
+        Timezone::Local
+    )
+    .is_err());
+    assert!(Recurrence::parse(
+        "2022-12-01T09:00:00~DAILY:interval@0#~INDEFINETELY:null",
+        Timezone::Local
+    )
+    .is_err());
+    assert!(Recurrence::parse("garbage", Timezone::Local).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn scheduled_transactions_become_reminders() -> Result<()> {
+    let mut rent = transaction(1, 1, 0, 1, -2000000);
+    rent.is_template = 2;
+    rent.template_name = Some("Rent".to_owned());
+    rent.recurrence = Some("2022-12-01T09:00:00~MONTHLY:interval@1#~EXACTLY_TIMES:12".to_owned());
+    let mut template = transaction(2, 1, 0, 1, -100);
+    template.is_template = 1;
+    let data = financisto(vec![rent, template]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+
+    // the reminder is named after its template, it has no note
+    assert_eq!(statements.len(), 2);
+    assert!(statements[0].contains("'Rent'"));
+    let reminder = &statements[1];
+    assert_eq!(value_of(reminder, 17), "1"); // reminderTransaction
+    assert_eq!(value_of(reminder, 18), value_of(reminder, 0)); // reminderGroupID
+    assert_eq!(value_of(reminder, 19), REMINDER_MONTHLY.to_string());
+    assert_eq!(value_of(reminder, 20), "1");
+    assert_eq!(value_of(reminder, 21), REMINDER_ENDS_AFTER.to_string());
+    assert_eq!(value_of(reminder, 22), "2022-12-01 09:00:00");
+    assert_eq!(value_of(reminder, 23), "NULL");
+    assert_eq!(value_of(reminder, 24), "12");
+
+    Ok(())
+}
+
+#[test]
+fn one_off_scheduled_transfer() -> Result<()> {
+    let mut transfer = transaction(1, 1, 2, 0, -100);
+    transfer.is_template = 2;
+    let data = financisto(vec![transfer]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+    let legs = statements
+        .iter()
+        .filter(|s| s.starts_with("INSERT INTO \"TRANSACTIONSTABLE\""))
+        .collect::<Vec<_>>();
+
+    assert_eq!(legs.len(), 2);
+    for leg in legs {
+        assert_eq!(value_of(leg, 17), "1");
+        assert_eq!(value_of(leg, 19), REMINDER_ONCE.to_string());
+        assert_eq!(value_of(leg, 24), "1");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn reminders_start_after_the_last_recurrence() -> Result<()> {
+    let timezone: Timezone = "Asia/Taipei".parse()?;
+    let mut rent = transaction(1, 1, 0, 1, -2000000);
+    rent.is_template = 2;
+    rent.recurrence = Some("2022-12-01T09:00:00~MONTHLY:interval@1#~EXACTLY_TIMES:12".to_owned());
+    // 2023-02-01 09:00 in Taipei
+    rent.last_recurrence = 1675213200000;
+
+    let recurrence = Recurrence::of(&rent, timezone)?.unwrap();
+    assert_eq!(
+        recurrence.start,
+        NaiveDate::from_ymd_opt(2023, 3, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    );
+    assert_eq!(recurrence.until, Until::Times(9));
+
+    // 2023-11-01 09:00, the twelfth and last occurrence
+    rent.last_recurrence = 1698800400000;
+    assert_eq!(Recurrence::of(&rent, timezone)?, None);
+
+    Ok(())
+}
+
+#[test]
+fn scheduled_split_is_one_reminder_group() -> Result<()> {
+    let mut split = transaction(1, 1, 0, SPLIT_CATEGORY_ID, -300);
+    split.is_template = 2;
+    let mut lunch = transaction(2, 1, 0, 1, -100);
+    lunch.parent_id = 1;
+    let mut transfer = transaction(3, 1, 2, 0, -200);
+    transfer.parent_id = 1;
+    transfer.to_amount = 200;
+    let data = financisto(vec![split, lunch, transfer]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+    let rows = statements
+        .iter()
+        .filter(|s| s.starts_with("INSERT INTO \"TRANSACTIONSTABLE\""))
+        .collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 3);
+    for row in rows.iter() {
+        assert_eq!(value_of(row, 17), "1");
+        assert_eq!(value_of(row, 18), value_of(rows[0], 15)); // the split
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unsupported_recurrences_are_errors() -> Result<()> {
+    let mut rent = transaction(1, 1, 0, 1, -2000000);
+    rent.is_template = 2;
+    rent.recurrence =
+        Some("2022-12-01T09:00:00~WEEKLY:interval@1#days@MON,FRI#~INDEFINETELY:null".to_owned());
+    let data = financisto(vec![rent, transaction(2, 1, 0, 1, -100)]);
+
+    let error = migrate_transactions(&data, &MigrateOptions::default())
+        .err()
+        .unwrap();
+    assert!(error.to_string().starts_with("scheduled transaction 1: "));
+
+    let repair = MigrateOptions {
+        integrity: Integrity::Repair,
+        ..Default::default()
+    };
+    assert_eq!(migrate_transactions(&data, &repair)?.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use financisto2bluecoins::*;
+
+fn entity(fields: &[(&str, &str)]) -> RawEntity {
+    fields
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<std::collections::HashMap<_, _>>()
+        .into()
+}
+
+#[test]
+fn known_schema_covers_the_model() {
+    let known = Schema::known();
+
+    for entity in [
+        "currency",
+        "account",
+        "category",
+        "payee",
+        "project",
+        "locations",
+    ] {
+        assert!(known.entities.contains_key(entity), "{}", entity);
+    }
+    assert!(known.entities["transactions"].contains("from_amount"));
+    assert_eq!(Schema::from_json(&known.to_json().unwrap()).unwrap(), known);
+}
+
+#[test]
+fn detect_schema_drift() {
+    let mut raw = RawEntities::new();
+    raw.push(entity(&[
+        ("entity", "payee"),
+        ("_id", "1"),
+        ("title", "7-Eleven"),
+        ("is_active", "1"),
+        ("updated_on", "0"),
+        ("last_category_id", "0"),
+    ]));
+    raw.push(entity(&[("entity", "sms_template"), ("_id", "1")]));
+    raw.push(entity(&[
+        ("entity", "currency"),
+        ("_id", "1"),
+        ("name", "TWD"),
+        ("title", "TWD"),
+        ("symbol", "$"),
+        ("symbol_format", "RS"),
+        ("decimals", "2"),
+        ("is_default", "1"),
+        ("is_active", "1"),
+        ("updated_on", "0"),
+        ("decimal_separators", "."),
+        ("rounding", "HALF_UP"),
+    ]));
+
+    let schema = Schema::of(&raw);
+    let changes = schema.diff(&Schema::known());
+
+    assert_eq!(schema.entities["payee"].len(), 6);
+    assert!(changes.contains(&SchemaChange::NewEntity {
+        entity: "sms_template".to_owned()
+    }));
+    assert!(changes.contains(&SchemaChange::MissingEntity {
+        entity: "budget".to_owned()
+    }));
+    let currency = changes
+        .iter()
+        .filter(|c| c.to_string().contains("currency."))
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        currency,
+        vec![
+            "field currency.decimal_separator renamed to decimal_separators",
+            "new field currency.rounding",
+            "missing field currency.group_separator",
+        ]
+    );
+    assert!(!changes.iter().any(|c| c.to_string().contains("payee")));
+}
+
+#[test]
+fn inspect_backup() -> Result<()> {
+    let backup = "PACKAGE:ru.orangesoft.financisto
+VERSION_CODE:117
+VERSION_NAME:1.8.4
+DATABASE_VERSION:218
+#START
+$ENTITY:project
+_id:1
+title:Trip
+is_active:1
+updated_on:0
+$$
+#END";
+    let lines = backup.lines().map(|l| l.to_owned()).collect::<Vec<_>>();
+
+    let schema = Schema::of(&parse_backup(backup)?.raw);
+    assert_eq!(schema, Schema::of(&convert_maps(&lines)?.raw));
+
+    assert_eq!(
+        schema.to_json()?,
+        "{\n  \"project\": [\n    \"_id\",\n    \"entity\",\n    \"is_active\",\n    \"title\",\n    \"updated_on\"\n  ]\n}"
+    );
+    assert!(schema
+        .diff(&Schema::known())
+        .iter()
+        .all(|c| matches!(c, SchemaChange::MissingEntity { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn new_column_in_a_backup() -> Result<()> {
+    let backup = "PACKAGE:ru.orangesoft.financisto
+VERSION_CODE:117
+VERSION_NAME:1.8.4
+DATABASE_VERSION:218
+#START
+$ENTITY:project
+_id:1
+title:Trip
+is_active:1
+updated_on:0
+color:#ff0000
+$$
+#END";
+    let lines = backup.lines().map(|l| l.to_owned()).collect::<Vec<_>>();
+
+    for data in [parse_backup(backup)?, convert_maps(&lines)?] {
+        let changes = Schema::of(&data.raw).diff(&Schema::known());
+
+        assert!(changes.contains(&SchemaChange::NewField {
+            entity: "project".to_owned(),
+            field: "color".to_owned(),
+        }));
+        assert_eq!(data.projects[0].updated_on, 0);
+    }
+
+    Ok(())
+}
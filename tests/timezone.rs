@@ -0,0 +1,94 @@
+use chrono::FixedOffset;
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+/// 2022-12-01 16:00:00 UTC, midnight in Taipei.
+const MIDNIGHT_IN_TAIPEI: i64 = 1669910400000;
+
+#[test]
+fn parse_timezones() -> Result<()> {
+    assert_eq!(
+        "Asia/Taipei".parse::<Timezone>()?,
+        Timezone::Named(chrono_tz::Asia::Taipei)
+    );
+    assert_eq!("UTC".parse::<Timezone>()?, Timezone::Named(chrono_tz::UTC));
+    assert_eq!(
+        "+08:00".parse::<Timezone>()?,
+        Timezone::Fixed(FixedOffset::east_opt(8 * 3600).unwrap())
+    );
+    assert_eq!(
+        "-0530".parse::<Timezone>()?,
+        Timezone::Fixed(FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap())
+    );
+    assert_eq!("local".parse::<Timezone>()?, Timezone::Local);
+    assert!("Mars/Olympus".parse::<Timezone>().is_err());
+    assert!("+99:00".parse::<Timezone>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn transaction_dates_follow_the_timezone() -> Result<()> {
+    let mut tx = transaction(1, 1, 0, 1, -100);
+    tx.datetime = MIDNIGHT_IN_TAIPEI;
+    let data = financisto(vec![tx]);
+
+    for (timezone, date) in [
+        ("Asia/Taipei", "2022-12-02 00:00:00"),
+        ("+08:00", "2022-12-02 00:00:00"),
+        ("UTC", "2022-12-01 16:00:00"),
+    ] {
+        let options = MigrateOptions {
+            timezone: timezone.parse()?,
+            ..Default::default()
+        };
+
+        let statements = migrate_transactions(&data, &options)?;
+
+        assert_eq!(value_of(&statements[0], 5), date, "{}", timezone);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn account_dates_follow_the_timezone() -> Result<()> {
+    let mut data = financisto(vec![]);
+    data.accounts[0].creation_date = MIDNIGHT_IN_TAIPEI;
+    let options = MigrateOptions {
+        timezone: "Asia/Taipei".parse()?,
+        ..Default::default()
+    };
+
+    let statements = migrate_accounts(&data, &options)?;
+    let init = statements
+        .iter()
+        .find(|s| s.starts_with("INSERT INTO \"TRANSACTIONSTABLE\""))
+        .unwrap();
+
+    assert_eq!(value_of(init, 5), "2022-12-02 00:00:00");
+
+    Ok(())
+}
+
+#[test]
+fn date_filter_uses_the_timezone() -> Result<()> {
+    let mut tx = transaction(1, 1, 0, 1, -100);
+    tx.datetime = MIDNIGHT_IN_TAIPEI;
+    let data = financisto(vec![tx]);
+    let filter = Filter {
+        from: chrono::NaiveDate::from_ymd_opt(2022, 12, 2),
+        ..Default::default()
+    };
+
+    let taipei = filter.resolve(&data, "Asia/Taipei".parse()?)?;
+    let utc = filter.resolve(&data, "UTC".parse()?)?;
+
+    assert!(taipei.transaction(&data.transactions[0]));
+    assert!(!utc.transaction(&data.transactions[0]));
+
+    Ok(())
+}
@@ -0,0 +1,339 @@
+use std::collections::HashSet;
+
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+#[test]
+fn system_categories_are_not_migrated() -> Result<()> {
+    let data = financisto(Vec::new());
+    let statements = migrate_categories(&data, &MigrateOptions::default())?;
+
+    assert!(!statements.iter().any(|s| s.contains("<NO_CATEGORY>")));
+    // Food, 收入 and the two uncategorized parents
+    assert_eq!(
+        statements
+            .iter()
+            .filter(|s| s.contains("PARENTCATEGORYTABLE"))
+            .count(),
+        4
+    );
+
+    Ok(())
+}
+
+#[test]
+fn uncategorized_transactions_use_dedicated_categories() -> Result<()> {
+    let data = financisto(vec![
+        transaction(1, 1, 0, NO_CATEGORY_ID, -100),
+        transaction(2, 1, 0, NO_CATEGORY_ID, 100),
+        transaction(3, 1, 0, 2, -100),
+    ]);
+    let ids = CategoryIds::new(&data.categories);
+    let categories = migrate_categories(&data, &MigrateOptions::default())?;
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+
+    assert_eq!(
+        value_of(&statements[0], 7),
+        ids.uncategorized_expense.to_string()
+    );
+    assert_eq!(
+        value_of(&statements[1], 7),
+        ids.uncategorized_income.to_string()
+    );
+    assert_eq!(value_of(&statements[2], 7), "21");
+
+    assert!(categories.iter().any(|s| s.contains(&format!(
+        "VALUES ('{}', '{}', '3'",
+        ids.uncategorized_expense, UNCATEGORIZED_NAME
+    ))));
+    assert!(categories.iter().any(|s| s.contains(&format!(
+        "VALUES ('{}', '{}', '2'",
+        ids.uncategorized_income, UNCATEGORIZED_NAME
+    ))));
+
+    Ok(())
+}
+
+#[test]
+fn unknown_category_is_an_error() {
+    let data = financisto(vec![transaction(1, 1, 0, 42, -100)]);
+
+    let error = migrate_transactions(&data, &MigrateOptions::default()).unwrap_err();
+
+    assert!(error.to_string().contains("unknown category 42"));
+}
+
+#[test]
+fn transfer_item_name_is_localized() -> Result<()> {
+    let data = financisto(vec![transaction(1, 1, 2, 0, -100)]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+    assert_eq!(value_of(&statements[0], 1), "轉帳");
+
+    let options = MigrateOptions {
+        item_names: ItemNames::for_language("en"),
+        ..Default::default()
+    };
+    let statements = migrate_transactions(&data, &options)?;
+    assert_eq!(value_of(&statements[0], 1), "Transfer");
+
+    Ok(())
+}
+
+#[test]
+fn transfer_item_name_from_account_titles() -> Result<()> {
+    let data = financisto(vec![
+        transaction(1, 1, 2, 0, -100),
+        transaction(2, 2, 1, 0, -100),
+    ]);
+    let options = MigrateOptions {
+        item_names: ItemNames {
+            transfer: "{from} → {to}".to_owned(),
+            ..ItemNames::for_language("en")
+        },
+        ..Default::default()
+    };
+
+    let statements = migrate_transactions(&data, &options)?;
+
+    assert_eq!(value_of(&statements[0], 1), "Cash → Bank");
+    assert_eq!(value_of(&statements[3], 1), "Bank → Cash");
+
+    Ok(())
+}
+
+#[test]
+fn unnamed_items_are_configurable() -> Result<()> {
+    let data = financisto(vec![
+        transaction(1, 1, 0, 1, -100),
+        transaction(2, 1, 0, 3, 100),
+    ]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+    assert_eq!(value_of(&statements[0], 1), "2");
+    assert_eq!(value_of(&statements[1], 1), "3");
+
+    let options = MigrateOptions {
+        item_names: ItemNames {
+            unnamed_expense: Some("Misc expense".to_owned()),
+            unnamed_income: Some("Misc income".to_owned()),
+            ..ItemNames::for_language("en")
+        },
+        ..Default::default()
+    };
+    let statements = migrate_transactions(&data, &options)?;
+    assert_eq!(value_of(&statements[0], 1), "Misc expense");
+    assert_eq!(value_of(&statements[1], 1), value_of(&statements[0], 0));
+    assert_eq!(value_of(&statements[2], 1), "Misc income");
+
+    Ok(())
+}
+
+#[test]
+fn status_is_mapped() -> Result<()> {
+    let mut reconciled = transaction(1, 1, 0, 1, -100);
+    reconciled.status = Some("RC".to_owned());
+    let mut cleared = transaction(2, 1, 2, 0, -100);
+    cleared.status = Some("CL".to_owned());
+    let mut unknown = transaction(3, 1, 0, 1, -100);
+    unknown.status = Some("XX".to_owned());
+    let data = financisto(vec![reconciled, cleared, unknown]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+    let statuses = statements
+        .iter()
+        .filter(|s| s.starts_with("INSERT INTO \"TRANSACTIONSTABLE\""))
+        .map(|s| value_of(s, 10).parse::<i32>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            STATUS_RECONCILED,
+            STATUS_CLEARED,
+            STATUS_CLEARED,
+            STATUS_NONE
+        ]
+    );
+
+    let options = MigrateOptions {
+        status: StatusMap {
+            unknown: STATUS_CLEARED,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let statements = migrate_transactions(&data, &options)?;
+    assert_eq!(value_of(statements.last().unwrap(), 10), "1");
+
+    Ok(())
+}
+
+#[test]
+fn credit_card_payments() -> Result<()> {
+    let mut payment = transaction(1, 2, 3, 0, -100);
+    payment.is_ccard_payment = 1;
+    let mut misdirected = transaction(2, 2, 1, 0, -100);
+    misdirected.is_ccard_payment = 1;
+    let mut data = financisto(vec![payment, misdirected]);
+    data.accounts.push(account(3, "Visa", "CREDIT_CARD"));
+
+    assert_eq!(
+        check_credit_card_payments(&data),
+        vec![CreditCardPaymentIssue {
+            transaction_id: 2,
+            account_id: 1,
+            account_type: Some("CASH".to_owned()),
+        }]
+    );
+
+    let options = MigrateOptions {
+        item_names: ItemNames::for_language("en"),
+        ..Default::default()
+    };
+    let statements = migrate_transactions(&data, &options)?;
+    assert_eq!(value_of(&statements[0], 1), "Credit card payment");
+    assert_eq!(value_of(&statements[1], 1), value_of(&statements[0], 0));
+    assert_eq!(value_of(&statements[1], 6), TYPE_TRANSFER.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn credit_card_payments_are_transfers() -> Result<()> {
+    let mut payment = transaction(1, 2, 3, 0, -100);
+    payment.is_ccard_payment = 1;
+    let mut data = financisto(vec![payment]);
+    data.accounts.push(account(3, "Visa", "CREDIT_CARD"));
+    let mut transfer = financisto(data.transactions.clone());
+    transfer.transactions[0].is_ccard_payment = 0;
+    transfer.accounts = data.accounts.clone();
+    let mut options = MigrateOptions::default();
+    options.item_names.credit_card_payment = options.item_names.transfer.clone();
+
+    // with the same item name, a payment is the very same rows as a plain transfer to the card
+    assert_eq!(
+        migrate_transactions(&data, &options)?,
+        migrate_transactions(&transfer, &options)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn transfer_notes_are_preserved() -> Result<()> {
+    let mut transfer = transaction(1, 1, 2, 0, -100);
+    transfer.note = Some("rent for Dec".to_owned());
+    let data = financisto(vec![transfer]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+
+    assert_eq!(value_of(&statements[1], 9), "rent for Dec");
+    assert_eq!(value_of(&statements[2], 9), "rent for Dec");
+
+    Ok(())
+}
+
+fn split() -> Financisto {
+    let mut parent = transaction(1, 1, 0, SPLIT_CATEGORY_ID, -300);
+    parent.note = Some("Costco".to_owned());
+    let mut milk = transaction(2, 1, 0, 2, -100);
+    milk.parent_id = 1;
+    milk.note = Some("milk".to_owned());
+    let mut other = transaction(3, 1, 0, 1, -200);
+    other.parent_id = 1;
+    financisto(vec![parent, milk, other])
+}
+
+#[test]
+fn split_parent_note_goes_to_lines() -> Result<()> {
+    let statements = migrate_transactions(&split(), &MigrateOptions::default())?;
+
+    assert_eq!(value_of(&statements[0], 1), "Costco");
+    assert_eq!(value_of(&statements[1], 9), "Costco / milk");
+    assert_eq!(value_of(&statements[2], 9), "Costco");
+
+    Ok(())
+}
+
+#[test]
+fn split_parent_note_as_item_only() -> Result<()> {
+    let options = MigrateOptions {
+        split_note: SplitNote::ItemOnly,
+        ..Default::default()
+    };
+    let statements = migrate_transactions(&split(), &options)?;
+
+    assert_eq!(value_of(&statements[1], 9), "milk");
+    assert_eq!(value_of(&statements[2], 9), "");
+
+    Ok(())
+}
+
+#[test]
+fn split_lines_typed_by_their_own_sign() -> Result<()> {
+    let parent = transaction(1, 1, 0, SPLIT_CATEGORY_ID, -300);
+    let mut purchase = transaction(2, 1, 0, 1, -400);
+    purchase.parent_id = 1;
+    let mut refund = transaction(3, 1, 0, 1, 200);
+    refund.parent_id = 1;
+    let mut transfer = transaction(4, 1, 2, 0, -100);
+    transfer.parent_id = 1;
+    let data = financisto(vec![parent, purchase, refund, transfer]);
+
+    let statements = migrate_transactions(&data, &MigrateOptions::default())?;
+    let rows = statements
+        .iter()
+        .filter(|s| s.starts_with("INSERT INTO \"TRANSACTIONSTABLE\""))
+        .collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 4);
+    assert_eq!(value_of(rows[0], 6), TYPE_EXPENSE.to_string());
+    assert_eq!(value_of(rows[0], 2), "-4000000");
+    assert_eq!(value_of(rows[1], 6), TYPE_INCOME.to_string());
+    assert_eq!(value_of(rows[1], 2), "2000000");
+
+    // the transfer line becomes both legs of a transfer
+    assert_eq!(value_of(rows[2], 6), TYPE_TRANSFER.to_string());
+    assert_eq!(value_of(rows[3], 6), TYPE_TRANSFER.to_string());
+    assert_eq!(value_of(rows[2], 8), "6");
+    assert_eq!(value_of(rows[3], 8), "7");
+    assert_eq!(value_of(rows[2], 16), value_of(rows[2], 0));
+    assert_eq!(value_of(rows[3], 16), value_of(rows[2], 0));
+    assert_eq!(value_of(rows[2], 13), value_of(rows[3], 0));
+    assert_eq!(value_of(rows[3], 13), value_of(rows[2], 0));
+    assert_eq!(value_of(rows[2], 15), "0");
+
+    // every row has its own ID
+    let ids = rows.iter().map(|r| value_of(r, 0)).collect::<HashSet<_>>();
+    assert_eq!(ids.len(), rows.len());
+
+    Ok(())
+}
+
+#[test]
+fn splits_at_the_same_time_get_their_own_ids() -> Result<()> {
+    let mut book = BackupBuilder::new();
+    let twd = book.currency("TWD");
+    let cash = book.account("Cash", "CASH", twd);
+    let food = book.category("Food");
+    for _ in 0..2 {
+        let split = book.split(cash);
+        split.datetime = 1669856400000;
+        let split = split.id;
+        book.split_line(split, food, -100);
+    }
+    let data = book.build();
+    let options = MigrateOptions::default();
+
+    let mut statements = migrate_accounts(&data, &options)?;
+    statements.append(&mut migrate_categories(&data, &options)?);
+    statements.append(&mut migrate_transactions(&data, &options)?);
+
+    // the ID of a split is not the ID of a line of another split
+    assert_eq!(verify_sql(&statements)?, vec![]);
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use financisto2bluecoins::*;
+
+mod common;
+
+use common::*;
+
+fn migrate() -> Result<Vec<String>> {
+    let parent = transaction(1, 1, 0, SPLIT_CATEGORY_ID, -300);
+    let mut line = transaction(2, 1, 0, 2, -200);
+    line.parent_id = 1;
+    let mut transfer_line = transaction(3, 1, 2, 0, -100);
+    transfer_line.parent_id = 1;
+    let mut salary = transaction(4, 2, 0, 3, 10000);
+    salary.note = Some("salary".to_owned());
+    let data = financisto(vec![
+        parent,
+        line,
+        transfer_line,
+        salary,
+        transaction(5, 2, 1, 0, -1000),
+    ]);
+    let options = MigrateOptions::default();
+
+    let mut statements = migrate_accounts(&data, &options)?;
+    statements.append(&mut migrate_categories(&data, &options)?);
+    statements.append(&mut migrate_transactions(&data, &options)?);
+    Ok(statements)
+}
+
+fn transactions(statements: &[String]) -> Vec<usize> {
+    statements
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.starts_with("INSERT INTO \"TRANSACTIONSTABLE\""))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[test]
+fn migrated_data_is_valid() -> Result<()> {
+    assert_eq!(verify_sql(&migrate()?)?, vec![]);
+
+    Ok(())
+}
+
+#[test]
+fn broken_data_is_caught() -> Result<()> {
+    let statements = migrate()?;
+    let rows = transactions(&statements);
+    let last = *rows.last().unwrap();
+
+    // a leg of the last transfer is lost, and a row is written twice
+    let mut broken = statements.clone();
+    broken.remove(last);
+    broken.push(statements[rows[0]].to_owned());
+    // an expense points to a category that was never created
+    let expense = rows
+        .iter()
+        .copied()
+        .find(|i| value_of(&statements[*i], 6) == TYPE_EXPENSE.to_string())
+        .unwrap();
+    broken[expense] = broken[expense].replacen("'21', '6'", "'99', '6'", 1);
+
+    let violations = verify_sql(&broken)?;
+
+    let group_id = value_of(&statements[last], 16).parse()?;
+    assert!(violations.contains(&Violation::TransferLegs { group_id, legs: 1 }));
+    assert!(violations.contains(&Violation::DuplicateTransaction {
+        transaction_id: value_of(&statements[rows[0]], 0).parse()?,
+    }));
+    assert!(violations.contains(&Violation::UnknownCategory {
+        transaction_id: value_of(&statements[expense], 0).parse()?,
+        category_id: 99,
+    }));
+    assert_eq!(violations.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn verify_sqlite_database() -> Result<()> {
+    let statements = migrate()?;
+    let path = std::env::temp_dir().join(format!("verify-{}.fydb", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let connection = rusqlite::Connection::open(&path)?;
+    let mut tables = HashSet::new();
+    for statement in statements.iter() {
+        let row = Insert::parse(statement)?;
+        if tables.insert(row.table.to_owned()) {
+            let columns = row
+                .columns
+                .iter()
+                .map(|c| format!("\"{}\"", c))
+                .collect::<Vec<_>>();
+            connection.execute(
+                &format!("CREATE TABLE \"{}\" ({})", row.table, columns.join(", ")),
+                [],
+            )?;
+        }
+        connection.execute(statement, [])?;
+    }
+    drop(connection);
+
+    let violations = verify_sqlite(&path)?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(violations, vec![]);
+
+    Ok(())
+}